@@ -0,0 +1,223 @@
+pub mod multicore {
+    use std::collections::{HashMap, VecDeque};
+
+    use crate::cpu_scheduler::scheduler::Process;
+
+    /// How ready processes are distributed between the cores
+    #[derive(Copy, Clone, Debug)]
+    pub enum QueueMode {
+        /// Single queue shared by all the cores
+        Global,
+        /// Every core has its own queue, new processes go to the least loaded one
+        PerCore {
+            /// Every `balance_period` ticks processes are moved from the most to the least loaded queues
+            balance_period: Option<u32>,
+            /// Idle core with an empty queue takes a process from the most loaded queue
+            work_stealing: bool,
+        },
+    }
+
+    #[derive(Copy, Clone, Debug)]
+    struct Running {
+        process: Process,
+        quantum_timer: u32,
+    }
+
+    /// Result of a multiprocessor simulation
+    #[derive(Debug, Default)]
+    pub struct MultiCoreRun {
+        /// (PID, completion time) of every process, in order of completion
+        pub completions: Vec<(u32, u32)>,
+        /// Number of ticks each core was running a process
+        pub busy: Vec<u32>,
+        /// Time at which the last process was completed
+        pub makespan: u32,
+        /// Number of times a process was dispatched on a different core than the one it last ran on
+        pub migrations: u32,
+        /// Tick of the first dispatch of every process, by PID
+        pub first_run: HashMap<u32, u32>,
+    }
+
+    impl MultiCoreRun {
+        pub fn report(&self) -> String {
+            let mut result = String::new();
+            result.push_str("Core;Busy;Utilization\n");
+            for (core, busy) in self.busy.iter().enumerate() {
+                result.push_str(&format!(
+                    "{};{};{:.4}\n",
+                    core,
+                    busy,
+                    *busy as f64 / self.makespan.max(1) as f64
+                ));
+            }
+            result.push_str(&format!("Migrations;--;{}\n", self.migrations));
+            result
+        }
+    }
+
+    /// K cores running in lock-step, each core serves its process until completion
+    /// or, if `quantum_time` is set, until the quantum is used up (RoundRobin)
+    ///
+    /// I/O bursts are not simulated, every process runs only its first CPU burst,
+    /// so Feeder skips multi-core runs for datasets with burst sequences
+    pub struct MultiCore {
+        pub cores: usize,
+        pub mode: QueueMode,
+        pub quantum_time: Option<u32>,
+    }
+
+    impl MultiCore {
+        pub fn new(cores: usize, mode: QueueMode, quantum_time: Option<u32>) -> MultiCore {
+            if cores == 0 {
+                panic!("MultiCore needs at least one core");
+            }
+            MultiCore {
+                cores,
+                mode,
+                quantum_time,
+            }
+        }
+
+        pub fn name(&self) -> String {
+            let quantum = match self.quantum_time {
+                Some(quantum_time) => format!("RoundRobin({})", quantum_time),
+                None => "FirstComeFirstServe".to_string(),
+            };
+            format!("MultiCore({}, {:?}, {})", self.cores, self.mode, quantum)
+        }
+
+        /// Index of the queue processes should be added to
+        fn least_loaded(queues: &[VecDeque<Process>], running: &[Option<Running>]) -> usize {
+            (0..queues.len())
+                .min_by_key(|&i| queues[i].len() + running[i].is_some() as usize)
+                .unwrap()
+        }
+
+        /// Move processes from the longest to the shortest queues until they differ by at most one
+        fn balance(queues: &mut [VecDeque<Process>]) {
+            loop {
+                let longest = (0..queues.len()).max_by_key(|&i| queues[i].len()).unwrap();
+                let shortest = (0..queues.len()).min_by_key(|&i| queues[i].len()).unwrap();
+                if queues[longest].len() <= queues[shortest].len() + 1 {
+                    break;
+                }
+                let process = queues[longest].pop_back().unwrap();
+                queues[shortest].push_back(process);
+            }
+        }
+
+        /// Simulate execution of the processes
+        ///
+        /// Same timing as in the single core Feeder is used: process arriving at tick `t`
+        /// can first run at tick `t + 1`, and is completed at the tick its last burst unit was run
+        ///
+        /// # Arguments
+        /// * `processes` - &[Process] - Processes to be run
+        ///
+        /// # Returns
+        /// * MultiCoreRun - Completion times and per-core statistics
+        pub fn simulate(&self, processes: &[Process]) -> MultiCoreRun {
+            let queue_count = match self.mode {
+                QueueMode::Global => 1,
+                QueueMode::PerCore { .. } => self.cores,
+            };
+            let mut queues: Vec<VecDeque<Process>> = vec![VecDeque::new(); queue_count];
+            let mut running: Vec<Option<Running>> = vec![None; self.cores];
+            let mut last_core: HashMap<u32, usize> = HashMap::new();
+            let mut arrivals = processes.to_vec();
+            arrivals.sort_by_key(|x| (x.arrival, x.pid));
+            let mut arrivals = VecDeque::from(arrivals);
+            let mut run = MultiCoreRun {
+                busy: vec![0; self.cores],
+                ..Default::default()
+            };
+            let mut timer = 0;
+            loop {
+                if arrivals.is_empty()
+                    && queues.iter().all(|x| x.is_empty())
+                    && running.iter().all(|x| x.is_none())
+                {
+                    break;
+                }
+
+                // Expired quanta go back to the queue of the core
+                for core in 0..self.cores {
+                    if let (Some(current), Some(quantum_time)) = (running[core], self.quantum_time)
+                    {
+                        if current.quantum_timer == quantum_time {
+                            queues[core.min(queue_count - 1)].push_back(current.process);
+                            running[core] = None;
+                        }
+                    }
+                }
+
+                if let QueueMode::PerCore {
+                    balance_period: Some(balance_period),
+                    ..
+                } = self.mode
+                {
+                    if timer % balance_period.max(1) == 0 {
+                        MultiCore::balance(&mut queues);
+                    }
+                }
+
+                for (core, slot) in running.iter_mut().enumerate() {
+                    if slot.is_some() {
+                        continue;
+                    }
+                    let queue = core.min(queue_count - 1);
+                    let mut next = queues[queue].pop_front();
+                    if next.is_none() {
+                        if let QueueMode::PerCore {
+                            work_stealing: true,
+                            ..
+                        } = self.mode
+                        {
+                            let victim = (0..queue_count).max_by_key(|&i| queues[i].len()).unwrap();
+                            next = queues[victim].pop_back();
+                        }
+                    }
+                    if let Some(process) = next {
+                        if let Some(&last) = last_core.get(&process.pid) {
+                            if last != core {
+                                run.migrations += 1;
+                            }
+                        }
+                        last_core.insert(process.pid, core);
+                        run.first_run.entry(process.pid).or_insert(timer);
+                        *slot = Some(Running {
+                            process,
+                            quantum_timer: 0,
+                        });
+                    }
+                }
+
+                for (core, slot) in running.iter_mut().enumerate() {
+                    if let Some(current) = slot.as_mut() {
+                        if current.process.burst > 0 {
+                            current.process.burst -= 1;
+                            current.quantum_timer += 1;
+                            run.busy[core] += 1;
+                        }
+                        if current.process.burst == 0 {
+                            run.completions.push((current.process.pid, timer));
+                            run.makespan = timer;
+                            *slot = None;
+                        }
+                    }
+                }
+
+                while arrivals.front().is_some_and(|x| x.arrival == timer) {
+                    let process = arrivals.pop_front().unwrap();
+                    let queue = match self.mode {
+                        QueueMode::Global => 0,
+                        QueueMode::PerCore { .. } => MultiCore::least_loaded(&queues, &running),
+                    };
+                    queues[queue].push_back(process);
+                }
+                timer += 1;
+            }
+            run
+        }
+    }
+}
@@ -1,14 +1,107 @@
 pub mod paging {
     use indexmap::IndexMap;
-    use std::usize;
+    use std::collections::{HashMap, VecDeque};
+
+    /// Result of a single page reference
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    pub enum PageOutcome {
+        /// Page was resident in the frame
+        Hit { frame: usize },
+        /// Page was loaded into the frame, replacing the victim if memory was full
+        Fault { frame: usize, victim: Option<u32> },
+    }
+
+    impl PageOutcome {
+        pub fn is_fault(&self) -> bool {
+            matches!(self, PageOutcome::Fault { .. })
+        }
+
+        pub fn frame(&self) -> usize {
+            match self {
+                PageOutcome::Hit { frame } | PageOutcome::Fault { frame, .. } => *frame,
+            }
+        }
+    }
 
     pub trait PagingAlgorithm {
         fn page_in(&mut self, page: u32) -> bool;
+        /// Reference the page for reading or writing, algorithms which don't track
+        /// dirty pages treat every reference as a read
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn access(&mut self, page: u32, _write: bool) -> bool {
+            self.page_in(page)
+        }
+        /// Reference the page and describe what happened to the frames
+        ///
+        /// Derived from the resident set before and after a Page Fault, the victim is the
+        /// first page which left the memory, algorithms letting pages go on their own
+        /// (like Working Set) can lose more of them, which only `resident` shows.
+        /// The frame of the page comes from `frame`
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be referenced
+        /// * `write` - bool - Whether the page is modified
+        ///
+        /// # Returns
+        /// * PageOutcome - Hit or Page Fault with the frame of the page
+        fn reference(&mut self, page: u32, write: bool) -> PageOutcome {
+            let before = self.resident();
+            let fault = self.access(page, write);
+            let frame = self
+                .frame(page)
+                .unwrap_or_else(|| panic!("{} didn't keep page {} resident", self.name(), page));
+            match fault {
+                true => {
+                    let after = self.resident();
+                    PageOutcome::Fault {
+                        frame,
+                        victim: before.into_iter().find(|x| !after.contains(x)),
+                    }
+                }
+                false => PageOutcome::Hit { frame },
+            }
+        }
+        /// Resident pages, indexed by the frame they occupy in algorithms with fixed frames,
+        /// in the order of the algorithm's own lists otherwise
+        fn resident(&self) -> Vec<u32>;
+        /// Frame the resident page occupies, a page keeps its frame until it's evicted
+        /// and the page loaded in its place takes it over
+        ///
+        /// Algorithms with fixed frames index `resident` by them, the rest override it
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be looked up
+        ///
+        /// # Returns
+        /// * Option<usize> - Frame of the page, None if it isn't resident
+        fn frame(&self, page: u32) -> Option<usize> {
+            self.resident().iter().position(|&x| x == page)
+        }
+        /// Number of dirty pages written back on eviction, if the algorithm tracks them
+        fn write_backs(&self) -> Option<u32> {
+            None
+        }
+        /// Number of resident pages modified since they were loaded or last written back,
+        /// if the algorithm tracks them
+        fn dirty_pages(&self) -> Option<u32> {
+            None
+        }
+        /// Number of page frames available to the algorithm
+        fn page_size(&self) -> usize;
+        /// Name of the algorithm used in the output
+        fn name(&self) -> String {
+            let name = std::any::type_name::<Self>();
+            name.rsplit("::").next().unwrap_or(name).to_string()
+        }
     }
 
     #[derive(Debug)]
     pub struct FirstInFirstOut {
         pub queue: Vec<Option<u32>>,
+        /// Frame of the oldest page, replaced on the next Page Fault once memory is full
+        pub hand: usize,
         pub page_size: usize,
     }
 
@@ -19,12 +112,21 @@ pub mod paging {
             } */
             FirstInFirstOut {
                 queue: Vec::new(),
+                hand: 0,
                 page_size: memory_size,
             }
         }
     }
 
     impl PagingAlgorithm for FirstInFirstOut {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.queue.iter().flatten().copied().collect()
+        }
+
         /// Add a new page to the page frame
         /// According to FirstInFirstOut algorithm
         ///
@@ -34,15 +136,14 @@ pub mod paging {
         /// # Returns
         /// * bool - True if algorith yielded a Page Fault, False otherwise
         fn page_in(&mut self, page: u32) -> bool {
-            if self.page_size != self.queue.len() {
-                // There is no additional check for page existence needed, due to FIFO nature
+            if self.queue.contains(&Some(page)) {
+                false
+            } else if self.page_size != self.queue.len() {
                 self.queue.push(Some(page));
                 true
-            } else if self.queue.contains(&Some(page)) {
-                false
             } else {
-                self.queue.remove(0);
-                self.queue.push(Some(page));
+                self.queue[self.hand] = Some(page);
+                self.hand = (self.hand + 1) % self.page_size;
                 true
             }
         }
@@ -66,6 +167,14 @@ pub mod paging {
     }
 
     impl PagingAlgorithm for LeastFrequentlyUsed {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.queue.iter().flatten().copied().collect()
+        }
+
         fn page_in(&mut self, page: u32) -> bool {
             if self.page_size != self.queue.len() && !self.queue.contains(&Some(page)) {
                 self.queue.push(Some(page));
@@ -86,14 +195,1617 @@ pub mod paging {
                         min_page = *page;
                     }
                 }
-                self.queue.retain(|x| x != &Some(min_page));
+                // The new page takes over the frame of the evicted one
+                let index = self
+                    .queue
+                    .iter()
+                    .position(|x| x == &Some(min_page))
+                    .unwrap();
                 self.frequency.shift_remove(&min_page);
                 // println!("{:?} - after remove", self.frequency);
-                self.queue.push(Some(page));
+                self.queue[index] = Some(page);
                 self.frequency.insert(page, 1);
                 // println!("{:?} - after insert", self.frequency);
                 true
             }
         }
     }
+
+    /// Entry of the recency list, linked by indices into the node vector
+    #[derive(Debug, Copy, Clone)]
+    struct RecencyNode {
+        page: u32,
+        prev: Option<usize>,
+        next: Option<usize>,
+    }
+
+    /// Exact Least Recently Used algorithm
+    ///
+    /// Frames are kept in a doubly linked list ordered from the least to the most recently used,
+    /// so both hits and evictions are O(1)
+    #[derive(Debug)]
+    pub struct LeastRecentlyUsed {
+        /// Node of every resident page
+        pub frames: HashMap<u32, usize>,
+        nodes: Vec<RecencyNode>,
+        /// Least recently used node
+        head: Option<usize>,
+        /// Most recently used node
+        tail: Option<usize>,
+        pub page_size: usize,
+    }
+
+    impl LeastRecentlyUsed {
+        pub fn new(memory_size: usize) -> LeastRecentlyUsed {
+            LeastRecentlyUsed {
+                frames: HashMap::new(),
+                nodes: Vec::new(),
+                head: None,
+                tail: None,
+                page_size: memory_size,
+            }
+        }
+
+        fn unlink(&mut self, index: usize) {
+            let RecencyNode { prev, next, .. } = self.nodes[index];
+            match prev {
+                Some(prev) => self.nodes[prev].next = next,
+                None => self.head = next,
+            }
+            match next {
+                Some(next) => self.nodes[next].prev = prev,
+                None => self.tail = prev,
+            }
+        }
+
+        fn push_back(&mut self, index: usize) {
+            self.nodes[index].prev = self.tail;
+            self.nodes[index].next = None;
+            match self.tail {
+                Some(tail) => self.nodes[tail].next = Some(index),
+                None => self.head = Some(index),
+            }
+            self.tail = Some(index);
+        }
+    }
+
+    impl PagingAlgorithm for LeastRecentlyUsed {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.nodes.iter().map(|x| x.page).collect()
+        }
+
+        /// Add a new page to the page frame
+        /// According to Least Recently Used algorithm
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be added
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn page_in(&mut self, page: u32) -> bool {
+            if let Some(&index) = self.frames.get(&page) {
+                self.unlink(index);
+                self.push_back(index);
+                return false;
+            }
+            let index = if self.nodes.len() != self.page_size {
+                self.nodes.push(RecencyNode {
+                    page,
+                    prev: None,
+                    next: None,
+                });
+                self.nodes.len() - 1
+            } else {
+                let index = self.head.unwrap();
+                self.unlink(index);
+                self.frames.remove(&self.nodes[index].page);
+                self.nodes[index].page = page;
+                index
+            };
+            self.frames.insert(page, index);
+            self.push_back(index);
+            true
+        }
+    }
+
+    /// Least Recently Used approximation with aging counters
+    ///
+    /// Every referenced page gets its reference bit set, every `interval` references
+    /// the reference bits are shifted into the most significant bit of 8-bit counters,
+    /// the page with the lowest counter is evicted
+    #[derive(Debug)]
+    pub struct AgingLeastRecentlyUsed {
+        pub frames: Vec<u32>,
+        pub counters: Vec<u8>,
+        pub referenced: Vec<bool>,
+        pub interval: u32,
+        references: u32,
+        pub page_size: usize,
+    }
+
+    impl AgingLeastRecentlyUsed {
+        pub fn new(memory_size: usize, interval: u32) -> AgingLeastRecentlyUsed {
+            if interval == 0 {
+                panic!("AgingLeastRecentlyUsed needs an interval of at least one reference");
+            }
+            AgingLeastRecentlyUsed {
+                frames: Vec::new(),
+                counters: Vec::new(),
+                referenced: Vec::new(),
+                interval,
+                references: 0,
+                page_size: memory_size,
+            }
+        }
+
+        fn age(&mut self) {
+            for (counter, referenced) in self.counters.iter_mut().zip(self.referenced.iter_mut()) {
+                *counter = (*counter >> 1) | ((*referenced as u8) << 7);
+                *referenced = false;
+            }
+        }
+    }
+
+    impl PagingAlgorithm for AgingLeastRecentlyUsed {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.frames.clone()
+        }
+
+        /// Add a new page to the page frame
+        /// According to the aging approximation of Least Recently Used algorithm
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be added
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn page_in(&mut self, page: u32) -> bool {
+            let fault = match self.frames.iter().position(|&x| x == page) {
+                Some(index) => {
+                    self.referenced[index] = true;
+                    false
+                }
+                None if self.frames.len() != self.page_size => {
+                    self.frames.push(page);
+                    self.counters.push(0);
+                    self.referenced.push(true);
+                    true
+                }
+                None => {
+                    // Page referenced in the current interval is newer than any counter tells
+                    let (index, _) = self
+                        .counters
+                        .iter()
+                        .zip(self.referenced.iter())
+                        .enumerate()
+                        .min_by_key(|(_, (counter, referenced))| (**referenced, **counter))
+                        .unwrap();
+                    self.frames[index] = page;
+                    self.counters[index] = 0;
+                    self.referenced[index] = true;
+                    true
+                }
+            };
+            self.references += 1;
+            if self.references.is_multiple_of(self.interval) {
+                self.age();
+            }
+            fault
+        }
+    }
+
+    /// Optimal (Belady's MIN) algorithm, evicts the page whose next use is the farthest away
+    ///
+    /// Needs the whole reference string in advance, so it's only usable as a baseline
+    #[derive(Debug)]
+    pub struct OptimalReplacement {
+        /// Resident pages with the position of their next use
+        pub frames: Vec<(u32, usize)>,
+        pub references: Vec<u32>,
+        /// Position of the next use of the page referenced at every position, `usize::MAX` if never
+        next_use: Vec<usize>,
+        position: usize,
+        pub page_size: usize,
+    }
+
+    impl OptimalReplacement {
+        pub fn new(memory_size: usize, references: Vec<u32>) -> OptimalReplacement {
+            let mut next_use = vec![usize::MAX; references.len()];
+            let mut seen: HashMap<u32, usize> = HashMap::new();
+            for (position, page) in references.iter().enumerate().rev() {
+                if let Some(&next) = seen.get(page) {
+                    next_use[position] = next;
+                }
+                seen.insert(*page, position);
+            }
+            OptimalReplacement {
+                frames: Vec::new(),
+                references,
+                next_use,
+                position: 0,
+                page_size: memory_size,
+            }
+        }
+    }
+
+    impl PagingAlgorithm for OptimalReplacement {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.frames.iter().map(|(page, _)| *page).collect()
+        }
+
+        /// Add a new page to the page frame
+        /// According to Optimal algorithm, pages have to follow the reference string
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be added
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn page_in(&mut self, page: u32) -> bool {
+            let position = self.position;
+            if self.references.get(position) != Some(&page) {
+                panic!(
+                    "OptimalReplacement got page {} at position {}, which doesn't follow its reference string",
+                    page, position
+                );
+            }
+            self.position += 1;
+            let next_use = self.next_use[position];
+            if let Some(frame) = self.frames.iter_mut().find(|(x, _)| *x == page) {
+                frame.1 = next_use;
+                return false;
+            }
+            if self.frames.len() == self.page_size {
+                let (index, _) = self
+                    .frames
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, (_, next))| *next)
+                    .unwrap();
+                self.frames[index] = (page, next_use);
+                return true;
+            }
+            self.frames.push((page, next_use));
+            true
+        }
+    }
+
+    /// Clock (Second-Chance) algorithm
+    ///
+    /// Frames form a circular buffer, the hand skips pages with the reference bit set,
+    /// clearing it, and evicts the first page without it
+    #[derive(Debug)]
+    pub struct Clock {
+        pub frames: Vec<u32>,
+        pub referenced: Vec<bool>,
+        pub dirty: Vec<bool>,
+        pub hand: usize,
+        pub page_size: usize,
+        write_backs: u32,
+    }
+
+    impl Clock {
+        pub fn new(memory_size: usize) -> Clock {
+            Clock {
+                frames: Vec::new(),
+                referenced: Vec::new(),
+                dirty: Vec::new(),
+                hand: 0,
+                page_size: memory_size,
+                write_backs: 0,
+            }
+        }
+    }
+
+    impl PagingAlgorithm for Clock {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.frames.clone()
+        }
+
+        fn page_in(&mut self, page: u32) -> bool {
+            self.access(page, false)
+        }
+
+        /// Reference the page for reading or writing
+        /// According to Clock algorithm
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be referenced
+        /// * `write` - bool - Whether the page is modified
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn access(&mut self, page: u32, write: bool) -> bool {
+            if let Some(index) = self.frames.iter().position(|&x| x == page) {
+                self.referenced[index] = true;
+                self.dirty[index] |= write;
+                return false;
+            }
+            if self.frames.len() != self.page_size {
+                self.frames.push(page);
+                self.referenced.push(true);
+                self.dirty.push(write);
+                return true;
+            }
+            while self.referenced[self.hand] {
+                self.referenced[self.hand] = false;
+                self.hand = (self.hand + 1) % self.page_size;
+            }
+            if self.dirty[self.hand] {
+                self.write_backs += 1;
+            }
+            self.frames[self.hand] = page;
+            self.referenced[self.hand] = true;
+            self.dirty[self.hand] = write;
+            self.hand = (self.hand + 1) % self.page_size;
+            true
+        }
+
+        fn write_backs(&self) -> Option<u32> {
+            Some(self.write_backs)
+        }
+
+        fn dirty_pages(&self) -> Option<u32> {
+            Some(self.dirty.iter().filter(|&&x| x).count() as u32)
+        }
+    }
+
+    /// Enhanced Second-Chance (Not Recently Used) algorithm
+    ///
+    /// Pages are classified by (referenced, dirty), the hand evicts the first page of the
+    /// lowest class, preferring clean pages so they don't have to be written back
+    #[derive(Debug)]
+    pub struct EnhancedSecondChance {
+        pub frames: Vec<u32>,
+        pub referenced: Vec<bool>,
+        pub dirty: Vec<bool>,
+        pub hand: usize,
+        pub page_size: usize,
+        write_backs: u32,
+    }
+
+    impl EnhancedSecondChance {
+        pub fn new(memory_size: usize) -> EnhancedSecondChance {
+            EnhancedSecondChance {
+                frames: Vec::new(),
+                referenced: Vec::new(),
+                dirty: Vec::new(),
+                hand: 0,
+                page_size: memory_size,
+                write_backs: 0,
+            }
+        }
+
+        /// Find the victim frame, each round first looks for a (0, 0) page without changing
+        /// anything, then for a (0, 1) page clearing the reference bits it passes
+        fn victim(&mut self) -> usize {
+            loop {
+                for dirty in [false, true] {
+                    for _ in 0..self.page_size {
+                        let hand = self.hand;
+                        self.hand = (self.hand + 1) % self.page_size;
+                        if !self.referenced[hand] && self.dirty[hand] == dirty {
+                            return hand;
+                        }
+                        if dirty {
+                            self.referenced[hand] = false;
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    impl PagingAlgorithm for EnhancedSecondChance {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.frames.clone()
+        }
+
+        fn page_in(&mut self, page: u32) -> bool {
+            self.access(page, false)
+        }
+
+        /// Reference the page for reading or writing
+        /// According to Enhanced Second-Chance algorithm
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be referenced
+        /// * `write` - bool - Whether the page is modified
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn access(&mut self, page: u32, write: bool) -> bool {
+            if let Some(index) = self.frames.iter().position(|&x| x == page) {
+                self.referenced[index] = true;
+                self.dirty[index] |= write;
+                return false;
+            }
+            if self.frames.len() != self.page_size {
+                self.frames.push(page);
+                self.referenced.push(true);
+                self.dirty.push(write);
+                return true;
+            }
+            let victim = self.victim();
+            if self.dirty[victim] {
+                self.write_backs += 1;
+            }
+            self.frames[victim] = page;
+            self.referenced[victim] = true;
+            self.dirty[victim] = write;
+            true
+        }
+
+        fn write_backs(&self) -> Option<u32> {
+            Some(self.write_backs)
+        }
+
+        fn dirty_pages(&self) -> Option<u32> {
+            Some(self.dirty.iter().filter(|&&x| x).count() as u32)
+        }
+    }
+
+    /// Frames of an algorithm which orders its pages in lists, so positions in the lists
+    /// don't tell the frames. Pages keep the frame they were loaded into, a new page takes
+    /// the first free frame, the one of the page it replaced unless more pages left
+    #[derive(Debug, Default)]
+    pub struct FrameTable {
+        pub frames: Vec<Option<u32>>,
+    }
+
+    impl FrameTable {
+        /// Free the frames of the pages which left the memory and load the new pages
+        ///
+        /// # Arguments
+        /// * `resident` - &[u32] - Pages resident after the reference
+        fn update(&mut self, resident: &[u32]) {
+            for frame in self.frames.iter_mut() {
+                if frame.is_some_and(|x| !resident.contains(&x)) {
+                    *frame = None;
+                }
+            }
+            for &page in resident {
+                if self.frames.contains(&Some(page)) {
+                    continue;
+                }
+                match self.frames.iter().position(|x| x.is_none()) {
+                    Some(index) => self.frames[index] = Some(page),
+                    None => self.frames.push(Some(page)),
+                }
+            }
+        }
+
+        fn position(&self, page: u32) -> Option<usize> {
+            self.frames.iter().position(|&x| x == Some(page))
+        }
+    }
+
+    /// Adaptive Replacement Cache
+    ///
+    /// Resident pages are split between T1 (seen once recently) and T2 (seen at least twice),
+    /// ghost lists B1 and B2 remember pages evicted from them, a hit in a ghost list moves
+    /// the target size `p` of T1 towards the list which would have kept the page.
+    /// Every list is ordered from the least to the most recently used page
+    #[derive(Debug)]
+    pub struct AdaptiveReplacementCache {
+        pub t1: VecDeque<u32>,
+        pub t2: VecDeque<u32>,
+        pub b1: VecDeque<u32>,
+        pub b2: VecDeque<u32>,
+        /// Target size of T1
+        pub p: usize,
+        slots: FrameTable,
+        pub page_size: usize,
+    }
+
+    impl AdaptiveReplacementCache {
+        pub fn new(memory_size: usize) -> AdaptiveReplacementCache {
+            AdaptiveReplacementCache {
+                t1: VecDeque::new(),
+                t2: VecDeque::new(),
+                b1: VecDeque::new(),
+                b2: VecDeque::new(),
+                p: 0,
+                slots: FrameTable::default(),
+                page_size: memory_size,
+            }
+        }
+
+        fn remove(list: &mut VecDeque<u32>, page: u32) -> bool {
+            match list.iter().position(|&x| x == page) {
+                Some(index) => {
+                    list.remove(index);
+                    true
+                }
+                None => false,
+            }
+        }
+
+        /// Evict the least recently used page of T1 or T2 into its ghost list
+        fn replace(&mut self, in_b2: bool) {
+            let from_t1 = !self.t1.is_empty()
+                && (self.t1.len() > self.p || (in_b2 && self.t1.len() == self.p));
+            if from_t1 || self.t2.is_empty() {
+                let page = self.t1.pop_front().unwrap();
+                self.b1.push_back(page);
+            } else {
+                let page = self.t2.pop_front().unwrap();
+                self.b2.push_back(page);
+            }
+        }
+    }
+
+    impl PagingAlgorithm for AdaptiveReplacementCache {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.t1.iter().chain(self.t2.iter()).copied().collect()
+        }
+
+        fn frame(&self, page: u32) -> Option<usize> {
+            self.slots.position(page)
+        }
+
+        /// Add a new page to the page frame
+        /// According to Adaptive Replacement Cache algorithm
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be added
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn page_in(&mut self, page: u32) -> bool {
+            let size = self.page_size;
+            if Self::remove(&mut self.t1, page) || Self::remove(&mut self.t2, page) {
+                self.t2.push_back(page);
+                return false;
+            }
+            if Self::remove(&mut self.b1, page) {
+                let delta = (self.b2.len() / (self.b1.len() + 1)).max(1);
+                self.p = (self.p + delta).min(size);
+                self.replace(false);
+                self.t2.push_back(page);
+                self.slots.update(&self.resident());
+                return true;
+            }
+            if Self::remove(&mut self.b2, page) {
+                let delta = (self.b1.len() / (self.b2.len() + 1)).max(1);
+                self.p = self.p.saturating_sub(delta);
+                self.replace(true);
+                self.t2.push_back(page);
+                self.slots.update(&self.resident());
+                return true;
+            }
+            let total = self.t1.len() + self.t2.len() + self.b1.len() + self.b2.len();
+            if self.t1.len() + self.b1.len() == size {
+                if self.t1.len() < size {
+                    self.b1.pop_front();
+                    self.replace(false);
+                } else {
+                    self.t1.pop_front();
+                }
+            } else if total >= size {
+                if total == 2 * size {
+                    self.b2.pop_front();
+                }
+                self.replace(false);
+            }
+            self.t1.push_back(page);
+            self.slots.update(&self.resident());
+            true
+        }
+    }
+
+    /// Full 2Q algorithm
+    ///
+    /// New pages enter the FIFO A1in, pages evicted from it are remembered in the ghost
+    /// FIFO A1out, only pages referenced again while in A1out are promoted to the LRU
+    /// list Am, so a single scan can't flush the frequently used pages
+    #[derive(Debug)]
+    pub struct TwoQueue {
+        pub a1_in: VecDeque<u32>,
+        pub a1_out: VecDeque<u32>,
+        /// Ordered from the least to the most recently used page
+        pub am: VecDeque<u32>,
+        /// Size of A1in above which it gives up its pages first
+        pub in_size: usize,
+        /// Number of pages remembered in A1out
+        pub out_size: usize,
+        slots: FrameTable,
+        pub page_size: usize,
+    }
+
+    impl TwoQueue {
+        /// Create 2Q with the sizes recommended by its authors, A1in a quarter
+        /// and A1out a half of the frames
+        pub fn new(memory_size: usize) -> TwoQueue {
+            TwoQueue {
+                a1_in: VecDeque::new(),
+                a1_out: VecDeque::new(),
+                am: VecDeque::new(),
+                in_size: (memory_size / 4).max(1),
+                out_size: (memory_size / 2).max(1),
+                slots: FrameTable::default(),
+                page_size: memory_size,
+            }
+        }
+
+        /// Free a frame for a new page if all of them are taken
+        fn reclaim(&mut self) {
+            if self.a1_in.len() + self.am.len() < self.page_size {
+                return;
+            }
+            if self.a1_in.len() > self.in_size || self.am.is_empty() {
+                let page = self.a1_in.pop_front().unwrap();
+                self.a1_out.push_back(page);
+                if self.a1_out.len() > self.out_size {
+                    self.a1_out.pop_front();
+                }
+            } else {
+                self.am.pop_front();
+            }
+        }
+    }
+
+    impl PagingAlgorithm for TwoQueue {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.a1_in.iter().chain(self.am.iter()).copied().collect()
+        }
+
+        fn frame(&self, page: u32) -> Option<usize> {
+            self.slots.position(page)
+        }
+
+        /// Add a new page to the page frame
+        /// According to 2Q algorithm
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be added
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn page_in(&mut self, page: u32) -> bool {
+            if let Some(index) = self.am.iter().position(|&x| x == page) {
+                self.am.remove(index);
+                self.am.push_back(page);
+                return false;
+            }
+            if self.a1_in.contains(&page) {
+                return false;
+            }
+            self.reclaim();
+            match self.a1_out.iter().position(|&x| x == page) {
+                Some(index) => {
+                    self.a1_out.remove(index);
+                    self.am.push_back(page);
+                }
+                None => self.a1_in.push_back(page),
+            }
+            self.slots.update(&self.resident());
+            true
+        }
+    }
+
+    /// Status of a page known to LIRS
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    pub enum LirsStatus {
+        /// Low inter-reference recency, always resident
+        Lir,
+        /// High inter-reference recency, resident in the HIR queue
+        ResidentHir,
+        /// High inter-reference recency, evicted but still tracked in the stack
+        NonResidentHir,
+    }
+
+    /// Low Inter-reference Recency Set algorithm
+    ///
+    /// Pages which were re-referenced within a short distance form the LIR set and stay
+    /// resident, the rest share a small queue of HIR frames. The stack orders pages by
+    /// recency from the bottom (front) to the top (back) and always has a LIR page at
+    /// the bottom, so a HIR page referenced again while in the stack is closer than
+    /// the oldest LIR page and takes its place
+    #[derive(Debug)]
+    pub struct LowInterReferenceRecencySet {
+        pub stack: VecDeque<u32>,
+        /// Resident HIR pages, evicted from the front
+        pub queue: VecDeque<u32>,
+        pub status: HashMap<u32, LirsStatus>,
+        /// Number of frames reserved for LIR pages
+        pub lir_size: usize,
+        lir_count: usize,
+        slots: FrameTable,
+        pub page_size: usize,
+    }
+
+    impl LowInterReferenceRecencySet {
+        /// Create LIRS with 1% of the frames, but at least one, reserved for HIR pages
+        pub fn new(memory_size: usize) -> LowInterReferenceRecencySet {
+            if memory_size < 2 {
+                panic!("LowInterReferenceRecencySet needs at least one LIR and one HIR frame");
+            }
+            LowInterReferenceRecencySet {
+                stack: VecDeque::new(),
+                queue: VecDeque::new(),
+                status: HashMap::new(),
+                lir_size: memory_size - (memory_size / 100).max(1),
+                lir_count: 0,
+                slots: FrameTable::default(),
+                page_size: memory_size,
+            }
+        }
+
+        fn remove(list: &mut VecDeque<u32>, page: u32) -> bool {
+            match list.iter().position(|&x| x == page) {
+                Some(index) => {
+                    list.remove(index);
+                    true
+                }
+                None => false,
+            }
+        }
+
+        fn move_to_top(&mut self, page: u32) {
+            Self::remove(&mut self.stack, page);
+            self.stack.push_back(page);
+        }
+
+        /// Remove HIR pages from the bottom of the stack, forgetting the non-resident ones
+        fn prune(&mut self) {
+            while let Some(&page) = self.stack.front() {
+                match self.status[&page] {
+                    LirsStatus::Lir => break,
+                    LirsStatus::ResidentHir => {}
+                    LirsStatus::NonResidentHir => {
+                        self.status.remove(&page);
+                    }
+                }
+                self.stack.pop_front();
+            }
+        }
+
+        /// Turn the LIR page at the bottom of the stack into a resident HIR page
+        fn demote_bottom(&mut self) {
+            if let Some(page) = self.stack.pop_front() {
+                self.status.insert(page, LirsStatus::ResidentHir);
+                self.queue.push_back(page);
+                self.prune();
+            }
+        }
+    }
+
+    impl PagingAlgorithm for LowInterReferenceRecencySet {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.stack
+                .iter()
+                .filter(|x| self.status[*x] == LirsStatus::Lir)
+                .chain(self.queue.iter())
+                .copied()
+                .collect()
+        }
+
+        fn frame(&self, page: u32) -> Option<usize> {
+            self.slots.position(page)
+        }
+
+        /// Add a new page to the page frame
+        /// According to LIRS algorithm
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be added
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn page_in(&mut self, page: u32) -> bool {
+            let status = self.status.get(&page).copied();
+            match status {
+                Some(LirsStatus::Lir) => {
+                    self.move_to_top(page);
+                    self.prune();
+                    return false;
+                }
+                Some(LirsStatus::ResidentHir) => {
+                    Self::remove(&mut self.queue, page);
+                    if self.stack.contains(&page) {
+                        self.move_to_top(page);
+                        self.status.insert(page, LirsStatus::Lir);
+                        self.demote_bottom();
+                    } else {
+                        self.stack.push_back(page);
+                        self.queue.push_back(page);
+                    }
+                    return false;
+                }
+                Some(LirsStatus::NonResidentHir) | None => {}
+            }
+            if self.lir_count + self.queue.len() == self.page_size {
+                let victim = self.queue.pop_front().unwrap();
+                match self.stack.contains(&victim) {
+                    true => self.status.insert(victim, LirsStatus::NonResidentHir),
+                    false => self.status.remove(&victim),
+                };
+            }
+            if self.lir_count < self.lir_size {
+                self.lir_count += 1;
+                self.move_to_top(page);
+                self.status.insert(page, LirsStatus::Lir);
+            } else if status == Some(LirsStatus::NonResidentHir) {
+                self.move_to_top(page);
+                self.status.insert(page, LirsStatus::Lir);
+                self.demote_bottom();
+            } else {
+                self.stack.push_back(page);
+                self.queue.push_back(page);
+                self.status.insert(page, LirsStatus::ResidentHir);
+            }
+            self.slots.update(&self.resident());
+            true
+        }
+    }
+
+    /// Page tracked by CLOCK-Pro, either resident or kept as metadata during its test period
+    #[derive(Debug, Copy, Clone)]
+    pub struct ClockProEntry {
+        pub page: u32,
+        pub hot: bool,
+        pub resident: bool,
+        pub referenced: bool,
+        /// Cold page referenced again during its test period becomes hot
+        pub test: bool,
+    }
+
+    /// CLOCK-Pro, the clock approximation of LIRS
+    ///
+    /// Hot, cold and non-resident cold pages share one circular list with three hands:
+    /// the cold hand evicts cold pages, the hot hand demotes hot pages and ends test
+    /// periods, the test hand ends test periods when too many non-resident pages are
+    /// tracked. The target number of cold frames adapts to the re-references during
+    /// test periods. New entries are inserted right behind the hot hand
+    #[derive(Debug)]
+    pub struct ClockPro {
+        pub entries: Vec<ClockProEntry>,
+        pub hand_hot: usize,
+        pub hand_cold: usize,
+        pub hand_test: usize,
+        /// Target number of frames holding cold pages
+        pub cold_target: usize,
+        hot_count: usize,
+        cold_count: usize,
+        non_resident_count: usize,
+        slots: FrameTable,
+        pub page_size: usize,
+    }
+
+    impl ClockPro {
+        pub fn new(memory_size: usize) -> ClockPro {
+            ClockPro {
+                entries: Vec::new(),
+                hand_hot: 0,
+                hand_cold: 0,
+                hand_test: 0,
+                cold_target: 1,
+                hot_count: 0,
+                cold_count: 0,
+                non_resident_count: 0,
+                slots: FrameTable::default(),
+                page_size: memory_size,
+            }
+        }
+
+        fn hands(&mut self) -> [&mut usize; 3] {
+            [&mut self.hand_hot, &mut self.hand_cold, &mut self.hand_test]
+        }
+
+        fn advance(&self, hand: usize) -> usize {
+            (hand + 1) % self.entries.len()
+        }
+
+        /// Remove the entry, hands pointing at it move to the next one
+        fn remove_at(&mut self, index: usize) {
+            self.entries.remove(index);
+            let len = self.entries.len();
+            for hand in self.hands() {
+                if *hand > index {
+                    *hand -= 1;
+                }
+                if *hand >= len {
+                    *hand = 0;
+                }
+            }
+        }
+
+        /// Insert the entry at the head of the list, right behind the hot hand
+        fn insert_head(&mut self, entry: ClockProEntry) {
+            let index = self.hand_hot;
+            self.entries.insert(index, entry);
+            let len = self.entries.len();
+            for hand in self.hands() {
+                if *hand >= index {
+                    *hand = (*hand + 1) % len;
+                }
+            }
+        }
+
+        fn hot_limit(&self) -> usize {
+            self.page_size.saturating_sub(self.cold_target)
+        }
+
+        fn adapt_cold_target(&mut self, grow: bool) {
+            let max = self.page_size.saturating_sub(1).max(1);
+            self.cold_target = match grow {
+                true => (self.cold_target + 1).min(max),
+                false => self.cold_target.saturating_sub(1).max(1),
+            };
+        }
+
+        fn promote(&mut self, page: u32) {
+            self.adapt_cold_target(true);
+            self.hot_count += 1;
+            self.insert_head(ClockProEntry {
+                page,
+                hot: true,
+                resident: true,
+                referenced: false,
+                test: false,
+            });
+            if self.hot_count > self.hot_limit() {
+                self.run_hand_hot();
+            }
+        }
+
+        /// Free a frame by evicting a cold page
+        fn run_hand_cold(&mut self) {
+            loop {
+                let index = self.hand_cold;
+                let entry = self.entries[index];
+                if entry.hot || !entry.resident {
+                    self.hand_cold = self.advance(index);
+                    continue;
+                }
+                if entry.referenced {
+                    self.remove_at(index);
+                    if entry.test {
+                        self.cold_count -= 1;
+                        self.promote(entry.page);
+                    } else {
+                        self.insert_head(ClockProEntry {
+                            referenced: false,
+                            test: true,
+                            ..entry
+                        });
+                    }
+                    continue;
+                }
+                self.cold_count -= 1;
+                if entry.test {
+                    self.entries[index].resident = false;
+                    self.non_resident_count += 1;
+                    self.hand_cold = self.advance(index);
+                    if self.non_resident_count > self.page_size {
+                        self.run_hand_test();
+                    }
+                } else {
+                    self.remove_at(index);
+                }
+                return;
+            }
+        }
+
+        /// Demote the first hot page without the reference bit, ending the test
+        /// periods of the cold pages on the way
+        fn run_hand_hot(&mut self) {
+            loop {
+                let index = self.hand_hot;
+                let entry = self.entries[index];
+                if entry.hot {
+                    self.hand_hot = self.advance(index);
+                    if entry.referenced {
+                        self.entries[index].referenced = false;
+                        continue;
+                    }
+                    self.entries[index].hot = false;
+                    self.hot_count -= 1;
+                    self.cold_count += 1;
+                    return;
+                }
+                if entry.test && self.end_test(index) {
+                    continue;
+                }
+                self.hand_hot = self.advance(index);
+            }
+        }
+
+        /// Forget the first non-resident page, ending the test periods on the way
+        fn run_hand_test(&mut self) {
+            loop {
+                let index = self.hand_test;
+                let entry = self.entries[index];
+                if !entry.hot && entry.test && self.end_test(index) {
+                    return;
+                }
+                self.hand_test = self.advance(index);
+            }
+        }
+
+        /// End the test period of the cold page without a re-reference
+        ///
+        /// # Returns
+        /// * bool - True if the page was non-resident and got removed from the list
+        fn end_test(&mut self, index: usize) -> bool {
+            self.adapt_cold_target(false);
+            self.entries[index].test = false;
+            if self.entries[index].resident {
+                return false;
+            }
+            self.remove_at(index);
+            self.non_resident_count -= 1;
+            true
+        }
+    }
+
+    impl PagingAlgorithm for ClockPro {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.entries
+                .iter()
+                .filter(|x| x.resident)
+                .map(|x| x.page)
+                .collect()
+        }
+
+        fn frame(&self, page: u32) -> Option<usize> {
+            self.slots.position(page)
+        }
+
+        /// Add a new page to the page frame
+        /// According to CLOCK-Pro algorithm
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be added
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn page_in(&mut self, page: u32) -> bool {
+            let position = self.entries.iter().position(|x| x.page == page);
+            if let Some(index) = position.filter(|&x| self.entries[x].resident) {
+                self.entries[index].referenced = true;
+                return false;
+            }
+            if self.hot_count + self.cold_count == self.page_size {
+                self.run_hand_cold();
+            }
+            // Hands may have removed or moved entries
+            match self.entries.iter().position(|x| x.page == page) {
+                Some(index) => {
+                    self.remove_at(index);
+                    self.non_resident_count -= 1;
+                    self.promote(page);
+                }
+                // Pages are hot until the hot frames fill up for the first time
+                None if self.cold_count == 0
+                    && self.non_resident_count == 0
+                    && self.hot_count < self.hot_limit() =>
+                {
+                    self.hot_count += 1;
+                    self.insert_head(ClockProEntry {
+                        page,
+                        hot: true,
+                        resident: true,
+                        referenced: false,
+                        test: false,
+                    });
+                }
+                None => {
+                    self.cold_count += 1;
+                    self.insert_head(ClockProEntry {
+                        page,
+                        hot: false,
+                        resident: true,
+                        referenced: false,
+                        test: true,
+                    });
+                }
+            }
+            self.slots.update(&self.resident());
+            true
+        }
+    }
+
+    /// Working Set algorithm
+    ///
+    /// Pages not referenced within the last `window` references (virtual time) leave
+    /// the memory on their own, so the resident set follows the working set of the
+    /// process. When the working set doesn't fit into the frames the least recently
+    /// used page is evicted
+    #[derive(Debug)]
+    pub struct WorkingSet {
+        /// Resident pages with the virtual time of their last reference
+        pub frames: Vec<(u32, u32)>,
+        pub window: u32,
+        time: u32,
+        slots: FrameTable,
+        pub page_size: usize,
+    }
+
+    impl WorkingSet {
+        pub fn new(memory_size: usize, window: u32) -> WorkingSet {
+            if window == 0 {
+                panic!("WorkingSet needs a window of at least one reference");
+            }
+            WorkingSet {
+                frames: Vec::new(),
+                window,
+                time: 0,
+                slots: FrameTable::default(),
+                page_size: memory_size,
+            }
+        }
+    }
+
+    impl PagingAlgorithm for WorkingSet {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        /// Pages of the working set at the current virtual time, filtered here as well
+        /// since `page_in` only drops the expired pages of `frames` on a reference
+        fn resident(&self) -> Vec<u32> {
+            self.frames
+                .iter()
+                .filter(|(_, last_use)| self.time - last_use < self.window)
+                .map(|(page, _)| *page)
+                .collect()
+        }
+
+        fn frame(&self, page: u32) -> Option<usize> {
+            self.slots.position(page)
+        }
+
+        /// Add a new page to the page frame
+        /// According to Working Set algorithm
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be added
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn page_in(&mut self, page: u32) -> bool {
+            self.time += 1;
+            let (time, window) = (self.time, self.window);
+            self.frames.retain(|(_, last_use)| time - last_use < window);
+            if let Some(frame) = self.frames.iter_mut().find(|(x, _)| *x == page) {
+                frame.1 = time;
+                // Pages may have left the working set
+                self.slots.update(&self.resident());
+                return false;
+            }
+            if self.frames.len() == self.page_size {
+                let (index, _) = self
+                    .frames
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, (_, last_use))| *last_use)
+                    .unwrap();
+                self.frames.remove(index);
+            }
+            self.frames.push((page, time));
+            self.slots.update(&self.resident());
+            true
+        }
+    }
+
+    /// WSClock, the clock approximation of Working Set algorithm
+    ///
+    /// On a Page Fault the hand clears the reference bits, stamping the pages with the
+    /// current virtual time, and replaces the first clean page older than `window`,
+    /// old dirty pages are written back and skipped. New frames are only taken when
+    /// no page has left the working set, so the resident set can stay below the frames
+    #[derive(Debug)]
+    pub struct WorkingSetClock {
+        pub frames: Vec<u32>,
+        pub referenced: Vec<bool>,
+        pub dirty: Vec<bool>,
+        /// Virtual time the page was last seen referenced by the hand
+        pub last_use: Vec<u32>,
+        pub hand: usize,
+        pub window: u32,
+        time: u32,
+        pub page_size: usize,
+        write_backs: u32,
+    }
+
+    impl WorkingSetClock {
+        pub fn new(memory_size: usize, window: u32) -> WorkingSetClock {
+            if window == 0 {
+                panic!("WorkingSetClock needs a window of at least one reference");
+            }
+            WorkingSetClock {
+                frames: Vec::new(),
+                referenced: Vec::new(),
+                dirty: Vec::new(),
+                last_use: Vec::new(),
+                hand: 0,
+                window,
+                time: 0,
+                page_size: memory_size,
+                write_backs: 0,
+            }
+        }
+
+        /// Sweep the clock once looking for a clean page outside of the working set
+        ///
+        /// # Returns
+        /// * Option<usize> - Frame to be replaced, None if a new frame should be taken
+        fn victim(&mut self) -> Option<usize> {
+            let mut fallback = None;
+            for _ in 0..self.frames.len() {
+                let hand = self.hand;
+                self.hand = (self.hand + 1) % self.frames.len();
+                if self.referenced[hand] {
+                    self.referenced[hand] = false;
+                    self.last_use[hand] = self.time;
+                    continue;
+                }
+                if self.time - self.last_use[hand] < self.window {
+                    fallback = fallback.or(Some(hand));
+                    continue;
+                }
+                if self.dirty[hand] {
+                    self.dirty[hand] = false;
+                    self.write_backs += 1;
+                    fallback = fallback.or(Some(hand));
+                    continue;
+                }
+                return Some(hand);
+            }
+            if self.frames.len() != self.page_size {
+                return None;
+            }
+            // Whole working set is resident, evict the first page which wasn't referenced
+            let victim = fallback.unwrap_or(self.hand);
+            if self.dirty[victim] {
+                self.write_backs += 1;
+            }
+            Some(victim)
+        }
+    }
+
+    impl PagingAlgorithm for WorkingSetClock {
+        fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.frames.clone()
+        }
+
+        fn page_in(&mut self, page: u32) -> bool {
+            self.access(page, false)
+        }
+
+        /// Reference the page for reading or writing
+        /// According to WSClock algorithm
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be referenced
+        /// * `write` - bool - Whether the page is modified
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn access(&mut self, page: u32, write: bool) -> bool {
+            self.time += 1;
+            if let Some(index) = self.frames.iter().position(|&x| x == page) {
+                self.referenced[index] = true;
+                self.dirty[index] |= write;
+                return false;
+            }
+            match self.victim() {
+                Some(index) => {
+                    self.frames[index] = page;
+                    self.referenced[index] = true;
+                    self.dirty[index] = write;
+                    self.last_use[index] = self.time;
+                }
+                None => {
+                    self.frames.push(page);
+                    self.referenced.push(true);
+                    self.dirty.push(write);
+                    self.last_use.push(self.time);
+                }
+            }
+            true
+        }
+
+        fn write_backs(&self) -> Option<u32> {
+            Some(self.write_backs)
+        }
+
+        fn dirty_pages(&self) -> Option<u32> {
+            Some(self.dirty.iter().filter(|&&x| x).count() as u32)
+        }
+    }
+
+    pub struct PageFaultFrequencyConfig {
+        /// Frames given to the algorithm at the start
+        pub initial_frames: usize,
+        pub min_frames: usize,
+        pub max_frames: usize,
+        /// Number of the last references the fault rate is measured over
+        pub window: usize,
+        /// Fault rate below which a frame is taken away
+        pub lower: f64,
+        /// Fault rate above which a frame is added
+        pub upper: f64,
+    }
+
+    /// Page Fault Frequency controller, gives a variable number of frames to any algorithm
+    ///
+    /// Once `window` references are measured, a fault rate above `upper` adds a frame and
+    /// a rate below `lower` takes one away, then the measurement starts over. Algorithms
+    /// have a fixed number of frames, so the resized one is created anew and warmed up
+    /// by replaying the measured references with their write flags. The replay only
+    /// approximates the state of the replaced algorithm, frequencies, reference bits and
+    /// aging counters only know the replayed references. Dirty pages of the replaced
+    /// algorithm are written back
+    pub struct PageFaultFrequency {
+        pub config: PageFaultFrequencyConfig,
+        /// Creates the wrapped algorithm with the given number of frames
+        factory: Box<dyn Fn(usize) -> Box<dyn PagingAlgorithm>>,
+        algorithm: Box<dyn PagingAlgorithm>,
+        /// References of the current measurement with their write flags
+        history: Vec<(u32, bool)>,
+        /// Page Faults of the current measurement
+        faults: usize,
+        /// Number of frames after every reference
+        pub timeline: Vec<usize>,
+        /// Write-backs of the replaced algorithms, including their dirty pages
+        write_backs: u32,
+        /// Write-backs of the current algorithm during the replay, which didn't happen
+        replayed_write_backs: u32,
+    }
+
+    impl PageFaultFrequency {
+        pub fn new(
+            config: PageFaultFrequencyConfig,
+            factory: impl Fn(usize) -> Box<dyn PagingAlgorithm> + 'static,
+        ) -> PageFaultFrequency {
+            if config.min_frames > config.initial_frames
+                || config.initial_frames > config.max_frames
+            {
+                panic!("PageFaultFrequency needs min_frames <= initial_frames <= max_frames");
+            }
+            if config.window == 0 || config.lower > config.upper {
+                panic!("PageFaultFrequency needs a non-empty window and lower <= upper");
+            }
+            PageFaultFrequency {
+                algorithm: factory(config.initial_frames),
+                factory: Box::new(factory),
+                config,
+                history: Vec::new(),
+                faults: 0,
+                timeline: Vec::new(),
+                write_backs: 0,
+                replayed_write_backs: 0,
+            }
+        }
+
+        fn resize(&mut self, frames: usize) {
+            let mut algorithm = (self.factory)(frames);
+            for (page, write) in self.history.iter() {
+                algorithm.access(*page, *write);
+            }
+            self.write_backs += self.algorithm.write_backs().unwrap_or(0)
+                - self.replayed_write_backs
+                + self.algorithm.dirty_pages().unwrap_or(0);
+            self.replayed_write_backs = algorithm.write_backs().unwrap_or(0);
+            self.algorithm = algorithm;
+        }
+    }
+
+    impl PagingAlgorithm for PageFaultFrequency {
+        /// Number of frames currently given to the algorithm
+        fn page_size(&self) -> usize {
+            self.algorithm.page_size()
+        }
+
+        fn resident(&self) -> Vec<u32> {
+            self.algorithm.resident()
+        }
+
+        /// Frame in the wrapped algorithm, a resized one loads the pages anew
+        fn frame(&self, page: u32) -> Option<usize> {
+            self.algorithm.frame(page)
+        }
+
+        fn page_in(&mut self, page: u32) -> bool {
+            self.access(page, false)
+        }
+
+        /// Reference the page through the wrapped algorithm, adjusting its frames
+        /// According to Page Fault Frequency
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Page number to be referenced
+        /// * `write` - bool - Whether the page is modified
+        ///
+        /// # Returns
+        /// * bool - True if algorith yielded a Page Fault, False otherwise
+        fn access(&mut self, page: u32, write: bool) -> bool {
+            let fault = self.algorithm.access(page, write);
+            self.history.push((page, write));
+            self.faults += fault as usize;
+            if self.history.len() == self.config.window {
+                let rate = self.faults as f64 / self.config.window as f64;
+                let frames = self.page_size();
+                if rate > self.config.upper && frames < self.config.max_frames {
+                    self.resize(frames + 1);
+                } else if rate < self.config.lower && frames > self.config.min_frames {
+                    self.resize(frames - 1);
+                }
+                self.history.clear();
+                self.faults = 0;
+            }
+            self.timeline.push(self.page_size());
+            fault
+        }
+
+        fn write_backs(&self) -> Option<u32> {
+            self.algorithm
+                .write_backs()
+                .map(|write_backs| write_backs - self.replayed_write_backs + self.write_backs)
+        }
+
+        fn dirty_pages(&self) -> Option<u32> {
+            self.algorithm.dirty_pages()
+        }
+
+        fn name(&self) -> String {
+            format!("PageFaultFrequency<{}>", self.algorithm.name())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::custom_gen::lfu_recovery_sequence;
+        use crate::pager_gen::paging_data_generator::Feeder;
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        /// Assert that the algorithm faults less and stops faulting earlier than
+        /// LeastFrequentlyUsed on the LFU recovery workload whenever LFU doesn't recover,
+        /// that is, still faults in the last pass of the repeating sequence. When the
+        /// prefix made the repeating pages the frequent ones there is nothing to recover from
+        fn assert_recovers_faster(factory: fn(usize) -> Box<dyn PagingAlgorithm>) {
+            let mut stuck = 0;
+            for seed in 0..100 {
+                let (pages, _) = lfu_recovery_sequence(&mut StdRng::seed_from_u64(seed));
+                let last_pass = pages.len() - 5;
+                let feeder = Feeder::new(pages);
+                for n in 5..=7 {
+                    let lfu = feeder.fault_positions(&mut LeastFrequentlyUsed::new(n));
+                    if lfu.last() < Some(&last_pass) {
+                        continue;
+                    }
+                    stuck += 1;
+                    let mut function = factory(n);
+                    let faults = feeder.fault_positions(function.as_mut());
+                    let name = function.name();
+                    assert!(
+                        faults.len() < lfu.len(),
+                        "seed {seed}, frames {n}: {name} {} vs LFU {} page faults",
+                        faults.len(),
+                        lfu.len()
+                    );
+                    assert!(
+                        faults.last() < lfu.last(),
+                        "seed {seed}, frames {n}: {name} last page fault {:?} vs LFU {:?}",
+                        faults.last(),
+                        lfu.last()
+                    );
+                }
+            }
+            assert!(
+                stuck > 0,
+                "LFU recovered on every seed, nothing was compared"
+            );
+        }
+
+        #[test]
+        fn pages_keep_their_frame_while_resident() {
+            let factories: Vec<fn(usize) -> Box<dyn PagingAlgorithm>> = vec![
+                |n| Box::new(FirstInFirstOut::new(n)),
+                |n| Box::new(LeastFrequentlyUsed::new(n)),
+                |n| Box::new(AdaptiveReplacementCache::new(n)),
+                |n| Box::new(TwoQueue::new(n)),
+                |n| Box::new(LowInterReferenceRecencySet::new(n)),
+                |n| Box::new(ClockPro::new(n)),
+                |n| Box::new(WorkingSet::new(n, 10)),
+            ];
+            let (pages, _) = lfu_recovery_sequence(&mut StdRng::seed_from_u64(0));
+            for factory in factories {
+                let mut function = factory(4);
+                let name = function.name();
+                let mut frames: HashMap<u32, usize> = HashMap::new();
+                for (position, &page) in pages.iter().enumerate() {
+                    let frame = function.reference(page, false).frame();
+                    let resident = function.resident();
+                    frames.retain(|x, _| resident.contains(x) && *x != page);
+                    assert!(
+                        frames.values().all(|&x| x != frame),
+                        "{name} loaded page {page} at {position} into the taken frame {frame}"
+                    );
+                    frames.insert(page, frame);
+                    for x in resident {
+                        assert_eq!(
+                            function.frame(x),
+                            frames.get(&x).copied(),
+                            "{name} moved page {x}"
+                        );
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn page_fault_frequency_writes_back_dirty_pages_on_resize() {
+            let config = PageFaultFrequencyConfig {
+                initial_frames: 2,
+                min_frames: 2,
+                max_frames: 3,
+                window: 2,
+                lower: 0.0,
+                upper: 0.5,
+            };
+            let mut controller = PageFaultFrequency::new(config, |n| Box::new(Clock::new(n)));
+            controller.access(1, true);
+            controller.access(2, true);
+            assert_eq!(controller.page_size(), 3);
+            // Both dirty pages of the replaced Clock, the replay dirties them again
+            assert_eq!(controller.write_backs(), Some(2));
+            assert_eq!(controller.dirty_pages(), Some(2));
+        }
+
+        #[test]
+        fn adaptive_replacement_cache_recovers_faster_than_lfu() {
+            assert_recovers_faster(|n| Box::new(AdaptiveReplacementCache::new(n)));
+        }
+
+        #[test]
+        fn two_queue_recovers_faster_than_lfu() {
+            assert_recovers_faster(|n| Box::new(TwoQueue::new(n)));
+        }
+    }
 }
@@ -1,11 +1,103 @@
 pub mod scheduler {
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
     use serde::{Deserialize, Serialize};
+    use std::collections::{BTreeMap, HashMap};
 
     #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
     pub struct Process {
         pub pid: u32,
         pub arrival: u32,
         pub burst: u32,
+        /// Lower value means higher priority
+        #[serde(default)]
+        pub priority: u32,
+        /// Niceness in range -20..=19, lower value means bigger CPU share
+        #[serde(default)]
+        pub nice: i32,
+        /// Tickets held in proportional-share scheduling, at least 1
+        #[serde(default = "default_tickets")]
+        pub tickets: u32,
+        /// Absolute deadline of a real-time job
+        #[serde(default)]
+        pub deadline: Option<u32>,
+        /// Period of the task that released a real-time job
+        #[serde(default)]
+        pub period: Option<u32>,
+    }
+
+    fn default_tickets() -> u32 {
+        1
+    }
+
+    impl Default for Process {
+        fn default() -> Self {
+            Process {
+                pid: 0,
+                arrival: 0,
+                burst: 0,
+                priority: 0,
+                nice: 0,
+                tickets: default_tickets(),
+                deadline: None,
+                period: None,
+            }
+        }
+    }
+
+    /// Periodic real-time task, releasing a job every `period` ticks
+    #[derive(Copy, Clone, Debug, Serialize, Deserialize)]
+    pub struct PeriodicTask {
+        pub id: u32,
+        pub period: u32,
+        /// Worst case execution time of every job
+        pub wcet: u32,
+        /// Deadline relative to the release of the job
+        pub deadline: u32,
+    }
+
+    fn gcd(a: u32, b: u32) -> u32 {
+        if b == 0 {
+            a
+        } else {
+            gcd(b, a % b)
+        }
+    }
+
+    /// Least common multiple of all task periods, after which the schedule repeats itself
+    pub fn hyperperiod(tasks: &[PeriodicTask]) -> u32 {
+        tasks
+            .iter()
+            .fold(1, |acc, task| acc / gcd(acc, task.period) * task.period)
+    }
+
+    /// Release jobs of all the tasks in range 0..horizon
+    ///
+    /// # Arguments
+    /// * `tasks` - &[PeriodicTask] - Tasks releasing the jobs
+    /// * `horizon` - u32 - Time of the simulation, no jobs are released at or after it
+    ///
+    /// # Returns
+    /// * Vec<Process> - Jobs sorted by release time and task id, PIDs are assigned in that order
+    pub fn release_jobs(tasks: &[PeriodicTask], horizon: u32) -> Vec<Process> {
+        let mut jobs = Vec::new();
+        for task in tasks.iter() {
+            for release in (0..horizon).step_by(task.period.max(1) as usize) {
+                jobs.push((release, task.id, *task));
+            }
+        }
+        jobs.sort_by_key(|(release, id, _)| (*release, *id));
+        jobs.into_iter()
+            .enumerate()
+            .map(|(i, (release, _, task))| Process {
+                pid: i as u32,
+                arrival: release,
+                burst: task.wcet,
+                deadline: Some(release + task.deadline),
+                period: Some(task.period),
+                ..Default::default()
+            })
+            .collect()
     }
 
     pub fn process_table_header() -> String {
@@ -35,6 +127,49 @@ pub mod scheduler {
     pub trait Cpu {
         fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>);
         fn get_stack(&self) -> &Vec<Process>;
+        /// Name of the algorithm used in exported file names
+        fn name(&self) -> String {
+            let name = std::any::type_name::<Self>();
+            name.rsplit("::").next().unwrap_or(name).to_string()
+        }
+        /// Algorithm specific statistics appended to the output table, if any
+        fn report(&self) -> Option<String> {
+            None
+        }
+        /// Header of the DEBUG process table
+        fn table_header(&self) -> String {
+            process_table_header()
+        }
+        /// Rows of the DEBUG process table for the current state of the stack
+        fn table(&self, time: &u32) -> Vec<String> {
+            process_table(self.get_stack(), time)
+        }
+        /// Number of following loops in which, unless a process arrives, the process at the front
+        /// of the stack just keeps running (no completion, quantum expiry nor preemption),
+        /// `u32::MAX` while the stack is empty
+        ///
+        /// # Returns
+        /// * u32 - Number of loops that can be skipped, 0 if every loop has to be simulated
+        fn quiet_loops(&self) -> u32 {
+            0
+        }
+        /// Simulate the given number of quiet loops at once
+        ///
+        /// # Arguments
+        /// * `loops` - u32 - Number of loops to skip, never more than `quiet_loops`
+        fn skip_loops(&mut self, _loops: u32) {}
+    }
+
+    /// Quiet loops of a stack whose front process can't be preempted without new arrivals
+    fn quiet_front(stack: &[Process]) -> u32 {
+        stack.first().map_or(u32::MAX, |x| x.burst)
+    }
+
+    /// Run the process at the front of the stack for the given number of loops
+    fn run_front(stack: &mut [Process], loops: u32) {
+        if let Some(process) = stack.first_mut() {
+            process.burst -= loops;
+        }
     }
 
     pub struct FirstComeFirstServe {
@@ -81,22 +216,67 @@ pub mod scheduler {
         fn get_stack(&self) -> &Vec<Process> {
             &self.stack
         }
+
+        fn quiet_loops(&self) -> u32 {
+            quiet_front(&self.stack)
+        }
+
+        fn skip_loops(&mut self, loops: u32) {
+            run_front(&mut self.stack, loops);
+        }
     }
 
     pub struct RoundRobin {
         pub stack: Vec<Process>,
         pub quantum_time: u32,
         pub quantum_timer: u32,
-        pub stall_arrival: Option<Process>,
     }
 
     impl RoundRobin {
         pub fn new(quantum_time: u32) -> RoundRobin {
+            if quantum_time == 0 {
+                panic!("RoundRobin needs a quantum of at least one tick");
+            }
             RoundRobin {
                 stack: Vec::new(),
                 quantum_time,
                 quantum_timer: 0,
-                stall_arrival: None,
+            }
+        }
+    }
+
+    /// Outcome of the quantum check of the process at the front of the stack
+    pub enum QuantumEvent {
+        Finished(Process),
+        Expired(Process),
+    }
+
+    impl RoundRobin {
+        /// Check if the process at the front of the stack was done or used up its quantum
+        /// in the previous loop, if so it's removed from the stack and returned
+        pub fn check_quantum(&mut self) -> Option<QuantumEvent> {
+            let process = *self.stack.first()?;
+            if process.burst == 0 {
+                let process = self.stack.remove(0);
+                // Reset timer to prevent it from messing up, when the process is done in under
+                // k * quantum_time, where k is a positive
+                self.quantum_timer = 0;
+                Some(QuantumEvent::Finished(process))
+            } else if self.quantum_timer == self.quantum_time {
+                self.quantum_timer = 0;
+                Some(QuantumEvent::Expired(self.stack.remove(0)))
+            } else {
+                None
+            }
+        }
+
+        /// Simulate processing of the process at the front of the stack
+        pub fn run_quantum(&mut self) {
+            if let Some(process) = self.stack.first_mut() {
+                if process.burst > 0 {
+                    process.burst -= 1;
+                    self.quantum_timer += 1;
+                }
             }
         }
     }
@@ -104,29 +284,335 @@ pub mod scheduler {
     impl Cpu for RoundRobin {
         fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
             let mut pid = None;
-            let quantum_time = self.quantum_time;
-            if let Some(&process) = self.stack.first() {
-                // Check if the process was done in the previous loop
-                // remove process as first step instead of last for logging purposes
+            // Check if the process was done in the previous loop
+            // remove process as first step instead of last for logging purposes
+            match self.check_quantum() {
+                Some(QuantumEvent::Finished(process)) => pid = Some(process.pid),
+                Some(QuantumEvent::Expired(process)) => self.stack.push(process),
+                None => {}
+            }
+            self.run_quantum();
+            self.stack.append(&mut arrival.clone());
+            (timer + 1, pid)
+        }
+
+        fn get_stack(&self) -> &Vec<Process> {
+            &self.stack
+        }
+
+        fn quiet_loops(&self) -> u32 {
+            match self.stack.first() {
+                Some(process) => process.burst.min(self.quantum_time - self.quantum_timer),
+                None => u32::MAX,
+            }
+        }
+
+        fn skip_loops(&mut self, loops: u32) {
+            if !self.stack.is_empty() {
+                run_front(&mut self.stack, loops);
+                self.quantum_timer += loops;
+            }
+        }
+    }
+
+    /// Rule used to order processes with equal burst times
+    #[derive(Copy, Clone, Debug)]
+    pub enum TieBreak {
+        /// Earlier arrival first, then lower PID
+        Arrival,
+        /// Lower PID first
+        Pid,
+    }
+
+    impl TieBreak {
+        pub fn key(&self, process: &Process) -> (u32, u32) {
+            match self {
+                TieBreak::Arrival => (process.arrival, process.pid),
+                TieBreak::Pid => (process.pid, 0),
+            }
+        }
+    }
+
+    pub struct ShortestJobFirst {
+        pub stack: Vec<Process>,
+        pub tie_break: TieBreak,
+        running: bool,
+    }
+
+    impl ShortestJobFirst {
+        pub fn new(tie_break: TieBreak) -> ShortestJobFirst {
+            ShortestJobFirst {
+                stack: Vec::new(),
+                tie_break,
+                running: false,
+            }
+        }
+    }
 
-                // if (self.quantum_timer % quantum_time == 0) && self.quantum_timer != 0 {
+    impl Cpu for ShortestJobFirst {
+        /// Shortest Job First (non-preemptive) algorithm
+        ///
+        /// Once the running process is finished, the process with the smallest burst
+        /// is moved to the front of the stack and runs until completion
+        ///
+        /// # Arguments
+        /// * `arrival` - Vec<Process> - Processes to be added to the stack
+        /// * `timer` - u32 - Current timer state
+        ///
+        /// # Returns
+        /// * (new_timer, Option<pid>) - (u32, Option<u32>) - New timer state and PID of the process, if the process was finished (in previous loop)
+        fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
+            let mut pid = None;
+            if let Some(process) = self.stack.first() {
                 if process.burst == 0 {
                     let process = self.stack.remove(0);
-                    // Reset timer to prevent it from messing up, when the process is done in under
-                    // k * quantum_time, where k is a positive
-                    self.quantum_timer = 0;
+                    self.running = false;
                     pid = Some(process.pid);
-                } else if self.quantum_timer == quantum_time {
-                    self.quantum_timer = 0;
+                }
+            }
+
+            // Pick the next job only when the CPU is free, running job is never preempted
+            if !self.running && !self.stack.is_empty() {
+                let tie_break = self.tie_break;
+                let (index, _) = self
+                    .stack
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, x)| (x.burst, tie_break.key(x)))
+                    .unwrap();
+                let process = self.stack.remove(index);
+                self.stack.insert(0, process);
+                self.running = true;
+            }
+
+            if let Some(process) = self.stack.first_mut() {
+                if process.burst > 0 {
+                    process.burst -= 1;
+                }
+            }
+            self.stack.append(&mut arrival.clone());
+            (timer + 1, pid)
+        }
+
+        fn get_stack(&self) -> &Vec<Process> {
+            &self.stack
+        }
+
+        fn quiet_loops(&self) -> u32 {
+            // Next process is picked in the loop after the running one was finished
+            if self.running || self.stack.is_empty() {
+                quiet_front(&self.stack)
+            } else {
+                0
+            }
+        }
+
+        fn skip_loops(&mut self, loops: u32) {
+            run_front(&mut self.stack, loops);
+        }
+    }
+
+    pub struct ShortestRemainingTimeFirst {
+        pub stack: Vec<Process>,
+        pub tie_break: TieBreak,
+        pub preemptions: HashMap<u32, u32>,
+        running: Option<u32>,
+    }
+
+    impl ShortestRemainingTimeFirst {
+        pub fn new(tie_break: TieBreak) -> ShortestRemainingTimeFirst {
+            ShortestRemainingTimeFirst {
+                stack: Vec::new(),
+                tie_break,
+                preemptions: HashMap::new(),
+                running: None,
+            }
+        }
+    }
+
+    impl Cpu for ShortestRemainingTimeFirst {
+        /// Shortest Remaining Time First (preemptive SJF) algorithm
+        ///
+        /// Ready stack is re-evaluated every loop, running process is preempted
+        /// if any other process has strictly smaller remaining burst
+        ///
+        /// # Arguments
+        /// * `arrival` - Vec<Process> - Processes to be added to the stack
+        /// * `timer` - u32 - Current timer state
+        ///
+        /// # Returns
+        /// * (new_timer, Option<pid>) - (u32, Option<u32>) - New timer state and PID of the process, if the process was finished (in previous loop)
+        fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
+            let mut pid = None;
+            if let Some(process) = self.stack.first() {
+                if process.burst == 0 {
                     let process = self.stack.remove(0);
-                    self.stack.push(process);
+                    self.running = None;
+                    pid = Some(process.pid);
                 }
             }
+
+            if !self.stack.is_empty() {
+                let tie_break = self.tie_break;
+                let (index, shortest) = self
+                    .stack
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, x)| (x.burst, tie_break.key(x)))
+                    .map(|(i, x)| (i, *x))
+                    .unwrap();
+                match self.running {
+                    // Running process keeps the CPU on ties
+                    Some(running) if shortest.burst < self.stack[0].burst => {
+                        *self.preemptions.entry(running).or_insert(0) += 1;
+                        let process = self.stack.remove(index);
+                        self.stack.insert(0, process);
+                    }
+                    Some(_) => {}
+                    None => {
+                        let process = self.stack.remove(index);
+                        self.stack.insert(0, process);
+                    }
+                }
+                self.running = Some(self.stack[0].pid);
+            }
+
             if let Some(process) = self.stack.first_mut() {
-                // Simulate processing of the process
                 if process.burst > 0 {
                     process.burst -= 1;
-                    self.quantum_timer += 1;
+                }
+            }
+            // Every seen process is reported, including the never preempted ones
+            for process in arrival.iter() {
+                self.preemptions.entry(process.pid).or_insert(0);
+            }
+            self.stack.append(&mut arrival.clone());
+            (timer + 1, pid)
+        }
+
+        fn get_stack(&self) -> &Vec<Process> {
+            &self.stack
+        }
+
+        fn quiet_loops(&self) -> u32 {
+            match self.stack.first() {
+                // Remaining burst of the running process only gets shorter, so without
+                // arrivals it's preempted only if it already should be
+                Some(front)
+                    if self.running == Some(front.pid)
+                        && self.stack.iter().all(|x| x.burst >= front.burst) =>
+                {
+                    front.burst
+                }
+                Some(_) => 0,
+                None => u32::MAX,
+            }
+        }
+
+        fn skip_loops(&mut self, loops: u32) {
+            run_front(&mut self.stack, loops);
+        }
+
+        fn report(&self) -> Option<String> {
+            let mut preemptions = self.preemptions.iter().collect::<Vec<(&u32, &u32)>>();
+            preemptions.sort_by_key(|(pid, _)| **pid);
+            let mut result = String::new();
+            result.push_str("PID;Preemptions\n");
+            for (pid, count) in preemptions.iter() {
+                result.push_str(&format!("{};{}\n", pid, count));
+            }
+            result.push_str(&format!(
+                "Total;{}\n",
+                self.preemptions.values().sum::<u32>()
+            ));
+            Some(result)
+        }
+    }
+
+    /// Aging policy, priority of a waiting process is raised by `step`
+    /// every `interval` ticks it spends waiting in the stack
+    #[derive(Copy, Clone, Debug)]
+    pub struct Aging {
+        pub step: u32,
+        pub interval: u32,
+    }
+
+    pub struct PriorityScheduler {
+        pub stack: Vec<Process>,
+        pub preemptive: bool,
+        pub aging: Option<Aging>,
+        waiting: HashMap<u32, u32>,
+        running: bool,
+    }
+
+    impl PriorityScheduler {
+        pub fn new(preemptive: bool, aging: Option<Aging>) -> PriorityScheduler {
+            PriorityScheduler {
+                stack: Vec::new(),
+                preemptive,
+                aging,
+                waiting: HashMap::new(),
+                running: false,
+            }
+        }
+    }
+
+    impl Cpu for PriorityScheduler {
+        /// Priority scheduling algorithm, ties are resolved by arrival, then PID
+        ///
+        /// In preemptive mode the running process is preempted as soon as process with
+        /// strictly higher priority is present in the stack, otherwise the highest priority
+        /// process is picked only once the running one is finished
+        ///
+        /// # Arguments
+        /// * `arrival` - Vec<Process> - Processes to be added to the stack
+        /// * `timer` - u32 - Current timer state
+        ///
+        /// # Returns
+        /// * (new_timer, Option<pid>) - (u32, Option<u32>) - New timer state and PID of the process, if the process was finished (in previous loop)
+        fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
+            let mut pid = None;
+            if let Some(process) = self.stack.first() {
+                if process.burst == 0 {
+                    let process = self.stack.remove(0);
+                    self.waiting.remove(&process.pid);
+                    self.running = false;
+                    pid = Some(process.pid);
+                }
+            }
+
+            if !self.stack.is_empty() && (!self.running || self.preemptive) {
+                let (index, highest) = self
+                    .stack
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, x)| (x.priority, x.arrival, x.pid))
+                    .map(|(i, x)| (i, *x))
+                    .unwrap();
+                if !self.running || highest.priority < self.stack[0].priority {
+                    let process = self.stack.remove(index);
+                    self.stack.insert(0, process);
+                }
+                self.running = true;
+            }
+
+            if let Some(process) = self.stack.first_mut() {
+                if process.burst > 0 {
+                    process.burst -= 1;
+                }
+                // Time waited before it ran doesn't count towards the next wait
+                self.waiting.remove(&process.pid);
+            }
+
+            if let Some(aging) = self.aging {
+                // Every process except the running one is waiting
+                for process in self.stack.iter_mut().skip(1) {
+                    let waited = self.waiting.entry(process.pid).or_insert(0);
+                    *waited += 1;
+                    if *waited >= aging.interval {
+                        *waited = 0;
+                        process.priority = process.priority.saturating_sub(aging.step);
+                    }
                 }
             }
             self.stack.append(&mut arrival.clone());
@@ -136,5 +622,883 @@ pub mod scheduler {
         fn get_stack(&self) -> &Vec<Process> {
             &self.stack
         }
+
+        fn quiet_loops(&self) -> u32 {
+            match self.stack.first() {
+                // Aging changes priorities of the waiting processes every loop
+                Some(_) if self.aging.is_some() || !self.running => 0,
+                Some(front)
+                    if self.preemptive
+                        && self.stack.iter().any(|x| x.priority < front.priority) =>
+                {
+                    0
+                }
+                _ => quiet_front(&self.stack),
+            }
+        }
+
+        fn skip_loops(&mut self, loops: u32) {
+            run_front(&mut self.stack, loops);
+        }
+    }
+
+    #[derive(Clone, Debug)]
+    pub struct MultilevelFeedbackQueueConfig {
+        /// Quantum time of each level, first level has the highest priority
+        pub quanta: Vec<u32>,
+        /// Every `boost_period` ticks all processes are moved back to the first level
+        pub boost_period: Option<u32>,
+    }
+
+    pub struct MultilevelFeedbackQueue {
+        pub stack: Vec<Process>,
+        pub queues: Vec<RoundRobin>,
+        pub config: MultilevelFeedbackQueueConfig,
+        current_level: Option<usize>,
+        boost_timer: u32,
+    }
+
+    impl MultilevelFeedbackQueue {
+        pub fn new(config: MultilevelFeedbackQueueConfig) -> MultilevelFeedbackQueue {
+            if config.quanta.is_empty() {
+                panic!("MultilevelFeedbackQueue needs at least one level");
+            }
+            if config.boost_period == Some(0) {
+                panic!("MultilevelFeedbackQueue needs a boost period of at least one tick");
+            }
+            MultilevelFeedbackQueue {
+                stack: Vec::new(),
+                queues: config.quanta.iter().map(|&q| RoundRobin::new(q)).collect(),
+                config,
+                current_level: None,
+                boost_timer: 0,
+            }
+        }
+
+        /// Order in which the levels are flattened into the stack, level served in the last loop
+        /// goes first, so the process at the front of the stack is the one that ran, even when
+        /// new processes have just arrived to a higher level
+        fn stack_order(&self) -> Vec<usize> {
+            let mut order = (0..self.queues.len()).collect::<Vec<usize>>();
+            if let Some(level) = self.current_level {
+                order.remove(level);
+                order.insert(0, level);
+            }
+            order
+        }
+
+        /// Move every process back to the first level, keeping their relative order
+        fn boost(&mut self) {
+            let mut processes = Vec::new();
+            for queue in self.queues.iter_mut() {
+                processes.append(&mut queue.stack);
+                queue.quantum_timer = 0;
+            }
+            self.queues[0].stack = processes;
+            self.current_level = None;
+        }
+    }
+
+    impl Cpu for MultilevelFeedbackQueue {
+        /// Multilevel Feedback Queue algorithm
+        ///
+        /// Every level is a RoundRobin queue with its own quantum time, process using its
+        /// full quantum is demoted to the next level, the first non-empty level is always served
+        ///
+        /// # Arguments
+        /// * `arrival` - Vec<Process> - Processes to be added to the first level
+        /// * `timer` - u32 - Current timer state
+        ///
+        /// # Returns
+        /// * (new_timer, Option<pid>) - (u32, Option<u32>) - New timer state and PID of the process, if the process was finished (in previous loop)
+        fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
+            let mut pid = None;
+            let last_level = self.queues.len() - 1;
+            if let Some(level) = self.current_level {
+                match self.queues[level].check_quantum() {
+                    Some(QuantumEvent::Finished(process)) => pid = Some(process.pid),
+                    Some(QuantumEvent::Expired(process)) => {
+                        self.queues[(level + 1).min(last_level)].stack.push(process)
+                    }
+                    None => {}
+                }
+            }
+
+            if let Some(boost_period) = self.config.boost_period {
+                self.boost_timer += 1;
+                if self.boost_timer >= boost_period {
+                    self.boost_timer = 0;
+                    self.boost();
+                }
+            }
+
+            self.current_level = self.queues.iter().position(|x| !x.stack.is_empty());
+            if let Some(level) = self.current_level {
+                self.queues[level].run_quantum();
+            }
+            self.queues[0].stack.append(&mut arrival.clone());
+
+            self.stack = self
+                .stack_order()
+                .iter()
+                .flat_map(|&level| self.queues[level].stack.iter().copied())
+                .collect();
+            (timer + 1, pid)
+        }
+
+        fn get_stack(&self) -> &Vec<Process> {
+            &self.stack
+        }
+
+        fn quiet_loops(&self) -> u32 {
+            // Boost happens in the loop in which the boost timer reaches the boost period
+            let boost = self
+                .config
+                .boost_period
+                .map_or(u32::MAX, |x| x - self.boost_timer - 1);
+            let first = self.queues.iter().position(|x| !x.stack.is_empty());
+            match (first, self.current_level) {
+                (None, _) => boost,
+                (Some(first), Some(level)) if first == level => {
+                    self.queues[level].quiet_loops().min(boost)
+                }
+                _ => 0,
+            }
+        }
+
+        fn skip_loops(&mut self, loops: u32) {
+            if self.config.boost_period.is_some() {
+                self.boost_timer += loops;
+            }
+            match self.current_level {
+                Some(level) if !self.queues[level].stack.is_empty() => {
+                    self.queues[level].skip_loops(loops);
+                    run_front(&mut self.stack, loops);
+                }
+                _ => self.current_level = None,
+            }
+        }
+
+        fn table_header(&self) -> String {
+            format!("{};Level", process_table_header())
+        }
+
+        fn table(&self, time: &u32) -> Vec<String> {
+            let levels = self
+                .stack_order()
+                .into_iter()
+                .flat_map(|level| vec![level; self.queues[level].stack.len()]);
+            process_table(&self.stack, time)
+                .into_iter()
+                .zip(levels)
+                .map(|(row, level)| format!("{};{}", row, level))
+                .collect()
+        }
+    }
+
+    /// Load weights of nice levels -20..=19, same as `sched_prio_to_weight` in Linux
+    const NICE_TO_WEIGHT: [u64; 40] = [
+        88761, 71755, 56483, 46273, 36291, 29154, 23254, 18705, 14949, 11916, 9548, 7620, 6100,
+        4904, 3906, 3121, 2501, 1991, 1586, 1277, 1024, 820, 655, 526, 423, 335, 272, 215, 172,
+        137, 110, 87, 70, 56, 45, 36, 29, 23, 18, 15,
+    ];
+    const NICE_0_WEIGHT: u64 = 1024;
+
+    pub fn nice_to_weight(nice: i32) -> u64 {
+        NICE_TO_WEIGHT[(nice.clamp(-20, 19) + 20) as usize]
+    }
+
+    #[derive(Copy, Clone, Debug)]
+    struct SchedEntity {
+        process: Process,
+        vruntime: u64,
+        slice: u32,
+        ran: u32,
+    }
+
+    pub struct CompletelyFairScheduler {
+        pub stack: Vec<Process>,
+        pub target_latency: u32,
+        pub min_granularity: u32,
+        /// Runnable processes ordered by (vruntime, pid), leftmost one is picked next
+        runqueue: BTreeMap<(u64, u32), Process>,
+        running: Option<SchedEntity>,
+        min_vruntime: u64,
+        finished: Vec<(Process, u64)>,
+    }
+
+    impl CompletelyFairScheduler {
+        pub fn new(target_latency: u32, min_granularity: u32) -> CompletelyFairScheduler {
+            CompletelyFairScheduler {
+                stack: Vec::new(),
+                target_latency,
+                min_granularity,
+                runqueue: BTreeMap::new(),
+                running: None,
+                min_vruntime: 0,
+                finished: Vec::new(),
+            }
+        }
+
+        /// Slice of the scheduling period proportional to the weight of the process,
+        /// period is stretched when there are too many processes to fit in the target latency
+        fn slice(&self, process: &Process) -> u32 {
+            let nr_running = self.runqueue.len() as u64 + 1;
+            let period = (self.target_latency as u64).max(nr_running * self.min_granularity as u64);
+            let total_weight = self
+                .runqueue
+                .values()
+                .map(|x| nice_to_weight(x.nice))
+                .sum::<u64>()
+                + nice_to_weight(process.nice);
+            ((period * nice_to_weight(process.nice) / total_weight) as u32).max(1)
+        }
+
+        fn vruntimes(&self) -> Vec<u64> {
+            self.running
+                .iter()
+                .map(|x| x.vruntime)
+                .chain(self.runqueue.keys().map(|(vruntime, _)| *vruntime))
+                .collect()
+        }
+    }
+
+    impl Cpu for CompletelyFairScheduler {
+        /// Completely Fair Scheduler algorithm
+        ///
+        /// Running process accumulates virtual runtime scaled by the weight of its nice value,
+        /// once its slice is used up the process with the smallest vruntime is picked
+        ///
+        /// # Arguments
+        /// * `arrival` - Vec<Process> - Processes to be added to the runqueue
+        /// * `timer` - u32 - Current timer state
+        ///
+        /// # Returns
+        /// * (new_timer, Option<pid>) - (u32, Option<u32>) - New timer state and PID of the process, if the process was finished (in previous loop)
+        fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
+            let mut pid = None;
+            if let Some(entity) = self.running {
+                if entity.process.burst == 0 {
+                    self.finished.push((entity.process, entity.vruntime));
+                    self.running = None;
+                    pid = Some(entity.process.pid);
+                } else if entity.ran >= entity.slice {
+                    self.runqueue
+                        .insert((entity.vruntime, entity.process.pid), entity.process);
+                    self.running = None;
+                }
+            }
+
+            if self.running.is_none() {
+                if let Some(((vruntime, _), process)) = self.runqueue.pop_first() {
+                    self.running = Some(SchedEntity {
+                        process,
+                        vruntime,
+                        slice: self.slice(&process),
+                        ran: 0,
+                    });
+                }
+            }
+
+            if let Some(entity) = self.running.as_mut() {
+                if entity.process.burst > 0 {
+                    entity.process.burst -= 1;
+                    entity.vruntime +=
+                        NICE_0_WEIGHT * NICE_0_WEIGHT / nice_to_weight(entity.process.nice);
+                }
+                entity.ran += 1;
+            }
+            if let Some(&min) = self.vruntimes().iter().min() {
+                self.min_vruntime = self.min_vruntime.max(min);
+            }
+
+            // New processes start at min_vruntime, so they can't monopolize the CPU
+            for process in arrival {
+                self.runqueue
+                    .insert((self.min_vruntime, process.pid), process);
+            }
+            self.stack = self
+                .running
+                .iter()
+                .map(|x| x.process)
+                .chain(self.runqueue.values().copied())
+                .collect();
+            (timer + 1, pid)
+        }
+
+        fn get_stack(&self) -> &Vec<Process> {
+            &self.stack
+        }
+
+        fn quiet_loops(&self) -> u32 {
+            match self.running {
+                Some(entity) if entity.ran < entity.slice => {
+                    entity.process.burst.min(entity.slice - entity.ran)
+                }
+                Some(_) => 0,
+                None if self.runqueue.is_empty() => u32::MAX,
+                None => 0,
+            }
+        }
+
+        fn skip_loops(&mut self, loops: u32) {
+            if let Some(entity) = self.running.as_mut() {
+                entity.process.burst -= loops;
+                // Vruntime is rounded every loop, same as in `next_loop`
+                entity.vruntime += loops as u64
+                    * (NICE_0_WEIGHT * NICE_0_WEIGHT / nice_to_weight(entity.process.nice));
+                entity.ran += loops;
+                run_front(&mut self.stack, loops);
+            }
+            // Vruntime of the running process only grows, so the minimum is the one after the last loop
+            if let Some(&min) = self.vruntimes().iter().min() {
+                self.min_vruntime = self.min_vruntime.max(min);
+            }
+        }
+
+        fn report(&self) -> Option<String> {
+            let mut finished = self.finished.clone();
+            finished.sort_by_key(|(process, _)| process.pid);
+            let mut result = String::new();
+            result.push_str("PID;Nice;Weight;Vruntime\n");
+            for (process, vruntime) in finished.iter() {
+                result.push_str(&format!(
+                    "{};{};{};{}\n",
+                    process.pid,
+                    process.nice,
+                    nice_to_weight(process.nice),
+                    vruntime
+                ));
+            }
+            Some(result)
+        }
+
+        fn table_header(&self) -> String {
+            format!("{};Vruntime", process_table_header())
+        }
+
+        fn table(&self, time: &u32) -> Vec<String> {
+            process_table(&self.stack, time)
+                .into_iter()
+                .zip(self.vruntimes())
+                .map(|(row, vruntime)| format!("{};{}", row, vruntime))
+                .collect()
+        }
+    }
+
+    /// Accounting of CPU share received by each process against the share entitled by its tickets
+    #[derive(Default)]
+    pub struct ShareAccount {
+        received: HashMap<u32, f64>,
+        entitled: HashMap<u32, f64>,
+    }
+
+    impl ShareAccount {
+        /// Record a single tick, the first process of the stack is the one that received the CPU
+        /// while every runnable process is entitled to tickets / total_tickets of the tick
+        pub fn tick(&mut self, stack: &[Process]) {
+            let total = stack.iter().map(|x| x.tickets.max(1)).sum::<u32>() as f64;
+            for process in stack.iter() {
+                *self.entitled.entry(process.pid).or_insert(0.0) +=
+                    process.tickets.max(1) as f64 / total;
+                self.received.entry(process.pid).or_insert(0.0);
+            }
+            if let Some(process) = stack.first() {
+                *self.received.entry(process.pid).or_insert(0.0) += 1.0;
+            }
+        }
+
+        pub fn report(&self) -> String {
+            let mut pids = self.entitled.keys().copied().collect::<Vec<u32>>();
+            pids.sort();
+            let mut result = String::new();
+            result.push_str("PID;Received;Entitled;Error\n");
+            let mut total_error = 0.0;
+            for pid in pids.iter() {
+                let received = self.received[pid];
+                let entitled = self.entitled[pid];
+                total_error += (received - entitled).abs();
+                result.push_str(&format!(
+                    "{};{};{:.2};{:.2}\n",
+                    pid,
+                    received,
+                    entitled,
+                    received - entitled
+                ));
+            }
+            result.push_str(&format!(
+                "Mean absolute error;--;--;{:.2}\n",
+                total_error / pids.len().max(1) as f64
+            ));
+            result
+        }
+    }
+
+    pub struct LotteryScheduler {
+        pub stack: Vec<Process>,
+        pub quantum_time: u32,
+        pub quantum_timer: u32,
+        pub shares: ShareAccount,
+        rng: StdRng,
+    }
+
+    impl LotteryScheduler {
+        pub fn new(quantum_time: u32, seed: u64) -> LotteryScheduler {
+            LotteryScheduler {
+                stack: Vec::new(),
+                quantum_time,
+                quantum_timer: 0,
+                shares: ShareAccount::default(),
+                rng: StdRng::seed_from_u64(seed),
+            }
+        }
+
+        /// Draw a winning ticket and move its holder to the front of the stack
+        fn draw(&mut self) {
+            let total = self.stack.iter().map(|x| x.tickets.max(1)).sum::<u32>();
+            let mut ticket = self.rng.gen_range(0..total);
+            for index in 0..self.stack.len() {
+                let tickets = self.stack[index].tickets.max(1);
+                if ticket < tickets {
+                    let process = self.stack.remove(index);
+                    self.stack.insert(0, process);
+                    return;
+                }
+                ticket -= tickets;
+            }
+        }
+    }
+
+    impl Cpu for LotteryScheduler {
+        /// Lottery scheduling algorithm
+        ///
+        /// At the start of every quantum a random ticket is drawn, process holding it gets the CPU
+        ///
+        /// # Arguments
+        /// * `arrival` - Vec<Process> - Processes to be added to the stack
+        /// * `timer` - u32 - Current timer state
+        ///
+        /// # Returns
+        /// * (new_timer, Option<pid>) - (u32, Option<u32>) - New timer state and PID of the process, if the process was finished (in previous loop)
+        fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
+            let mut pid = None;
+            if let Some(process) = self.stack.first() {
+                if process.burst == 0 {
+                    let process = self.stack.remove(0);
+                    self.quantum_timer = 0;
+                    pid = Some(process.pid);
+                } else if self.quantum_timer == self.quantum_time {
+                    self.quantum_timer = 0;
+                }
+            }
+
+            if self.quantum_timer == 0 && !self.stack.is_empty() {
+                self.draw();
+            }
+            self.shares.tick(&self.stack);
+            if let Some(process) = self.stack.first_mut() {
+                if process.burst > 0 {
+                    process.burst -= 1;
+                    self.quantum_timer += 1;
+                }
+            }
+            self.stack.append(&mut arrival.clone());
+            (timer + 1, pid)
+        }
+
+        fn get_stack(&self) -> &Vec<Process> {
+            &self.stack
+        }
+
+        fn quiet_loops(&self) -> u32 {
+            // Shares are accounted every loop, only idle loops are skipped
+            if self.stack.is_empty() {
+                u32::MAX
+            } else {
+                0
+            }
+        }
+
+        fn report(&self) -> Option<String> {
+            Some(self.shares.report())
+        }
+    }
+
+    /// Large constant divided by tickets to get the stride of a process
+    const STRIDE_CONSTANT: u64 = 1 << 20;
+
+    pub struct StrideScheduler {
+        pub stack: Vec<Process>,
+        pub quantum_time: u32,
+        pub quantum_timer: u32,
+        pub shares: ShareAccount,
+        /// Pass value of every process in the stack
+        pass: HashMap<u32, u64>,
+    }
+
+    impl StrideScheduler {
+        pub fn new(quantum_time: u32) -> StrideScheduler {
+            StrideScheduler {
+                stack: Vec::new(),
+                quantum_time,
+                quantum_timer: 0,
+                shares: ShareAccount::default(),
+                pass: HashMap::new(),
+            }
+        }
+    }
+
+    impl Cpu for StrideScheduler {
+        /// Stride scheduling algorithm
+        ///
+        /// At the start of every quantum process with the lowest pass value gets the CPU,
+        /// its pass is then advanced by its stride (inversely proportional to its tickets)
+        ///
+        /// # Arguments
+        /// * `arrival` - Vec<Process> - Processes to be added to the stack
+        /// * `timer` - u32 - Current timer state
+        ///
+        /// # Returns
+        /// * (new_timer, Option<pid>) - (u32, Option<u32>) - New timer state and PID of the process, if the process was finished (in previous loop)
+        fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
+            let mut pid = None;
+            if let Some(process) = self.stack.first() {
+                if process.burst == 0 {
+                    let process = self.stack.remove(0);
+                    self.pass.remove(&process.pid);
+                    self.quantum_timer = 0;
+                    pid = Some(process.pid);
+                } else if self.quantum_timer == self.quantum_time {
+                    self.quantum_timer = 0;
+                }
+            }
+
+            if self.quantum_timer == 0 && !self.stack.is_empty() {
+                let pass = &self.pass;
+                let (index, _) = self
+                    .stack
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, x)| (pass[&x.pid], x.pid))
+                    .unwrap();
+                let process = self.stack.remove(index);
+                self.stack.insert(0, process);
+            }
+            self.shares.tick(&self.stack);
+            if let Some(process) = self.stack.first_mut() {
+                if process.burst > 0 {
+                    process.burst -= 1;
+                    self.quantum_timer += 1;
+                    *self.pass.get_mut(&process.pid).unwrap() +=
+                        STRIDE_CONSTANT / process.tickets.max(1) as u64;
+                }
+            }
+
+            // New processes join with the lowest pass in the stack, so they can't monopolize the CPU
+            let min_pass = self.pass.values().min().copied().unwrap_or(0);
+            for process in arrival.iter() {
+                self.pass.insert(process.pid, min_pass);
+            }
+            self.stack.append(&mut arrival.clone());
+            (timer + 1, pid)
+        }
+
+        fn get_stack(&self) -> &Vec<Process> {
+            &self.stack
+        }
+
+        fn quiet_loops(&self) -> u32 {
+            // Shares are accounted every loop, only idle loops are skipped
+            if self.stack.is_empty() {
+                u32::MAX
+            } else {
+                0
+            }
+        }
+
+        fn report(&self) -> Option<String> {
+            Some(self.shares.report())
+        }
+    }
+
+    /// Move the process with the smallest key to the front of the stack,
+    /// process at the front is kept on ties to avoid needless preemptions
+    fn dispatch_min_by_key<K: Ord>(stack: &mut Vec<Process>, key: impl Fn(&Process) -> K) {
+        if let Some((index, _)) = stack.iter().enumerate().min_by_key(|(_, x)| key(x)) {
+            let process = stack.remove(index);
+            stack.insert(0, process);
+        }
+    }
+
+    pub struct EarliestDeadlineFirst {
+        pub stack: Vec<Process>,
+    }
+
+    impl EarliestDeadlineFirst {
+        pub fn new() -> EarliestDeadlineFirst {
+            EarliestDeadlineFirst { stack: Vec::new() }
+        }
+    }
+
+    impl Cpu for EarliestDeadlineFirst {
+        /// Earliest Deadline First (preemptive) algorithm
+        ///
+        /// Job with the closest absolute deadline is always running,
+        /// processes without a deadline are served last
+        ///
+        /// # Arguments
+        /// * `arrival` - Vec<Process> - Processes to be added to the stack
+        /// * `timer` - u32 - Current timer state
+        ///
+        /// # Returns
+        /// * (new_timer, Option<pid>) - (u32, Option<u32>) - New timer state and PID of the process, if the process was finished (in previous loop)
+        fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
+            let mut pid = None;
+            if let Some(process) = self.stack.first() {
+                if process.burst == 0 {
+                    let process = self.stack.remove(0);
+                    pid = Some(process.pid);
+                }
+            }
+            dispatch_min_by_key(&mut self.stack, |x| x.deadline.unwrap_or(u32::MAX));
+            if let Some(process) = self.stack.first_mut() {
+                if process.burst > 0 {
+                    process.burst -= 1;
+                }
+            }
+            self.stack.append(&mut arrival.clone());
+            (timer + 1, pid)
+        }
+
+        fn get_stack(&self) -> &Vec<Process> {
+            &self.stack
+        }
+
+        fn quiet_loops(&self) -> u32 {
+            let key = |x: &Process| x.deadline.unwrap_or(u32::MAX);
+            match self.stack.first() {
+                Some(front) if self.stack.iter().any(|x| key(x) < key(front)) => 0,
+                _ => quiet_front(&self.stack),
+            }
+        }
+
+        fn skip_loops(&mut self, loops: u32) {
+            run_front(&mut self.stack, loops);
+        }
+    }
+
+    pub struct RateMonotonic {
+        pub stack: Vec<Process>,
+    }
+
+    impl RateMonotonic {
+        pub fn new() -> RateMonotonic {
+            RateMonotonic { stack: Vec::new() }
+        }
+    }
+
+    impl Cpu for RateMonotonic {
+        /// Rate Monotonic (preemptive, static priority) algorithm
+        ///
+        /// Job of the task with the shortest period is always running,
+        /// processes without a period are served last
+        ///
+        /// # Arguments
+        /// * `arrival` - Vec<Process> - Processes to be added to the stack
+        /// * `timer` - u32 - Current timer state
+        ///
+        /// # Returns
+        /// * (new_timer, Option<pid>) - (u32, Option<u32>) - New timer state and PID of the process, if the process was finished (in previous loop)
+        fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
+            let mut pid = None;
+            if let Some(process) = self.stack.first() {
+                if process.burst == 0 {
+                    let process = self.stack.remove(0);
+                    pid = Some(process.pid);
+                }
+            }
+            dispatch_min_by_key(&mut self.stack, |x| {
+                (x.period.unwrap_or(u32::MAX), x.arrival)
+            });
+            if let Some(process) = self.stack.first_mut() {
+                if process.burst > 0 {
+                    process.burst -= 1;
+                }
+            }
+            self.stack.append(&mut arrival.clone());
+            (timer + 1, pid)
+        }
+
+        fn get_stack(&self) -> &Vec<Process> {
+            &self.stack
+        }
+
+        fn quiet_loops(&self) -> u32 {
+            let key = |x: &Process| (x.period.unwrap_or(u32::MAX), x.arrival);
+            match self.stack.first() {
+                Some(front) if self.stack.iter().any(|x| key(x) < key(front)) => 0,
+                _ => quiet_front(&self.stack),
+            }
+        }
+
+        fn skip_loops(&mut self, loops: u32) {
+            run_front(&mut self.stack, loops);
+        }
+    }
+
+    /// Response ratio (waiting + burst) / burst of a process which hasn't run yet
+    ///
+    /// Process arriving in loop `t` can first run in loop `t + 1`, so it waits from then on
+    pub fn response_ratio(process: &Process, timer: u32) -> f64 {
+        let waiting = timer.saturating_sub(process.arrival + 1);
+        let burst = process.burst.max(1);
+        (waiting + burst) as f64 / burst as f64
+    }
+
+    pub struct HighestResponseRatioNext {
+        pub stack: Vec<Process>,
+        running: bool,
+        /// Response ratio of the running process at the moment it was chosen
+        chosen_ratio: Option<f64>,
+    }
+
+    impl HighestResponseRatioNext {
+        pub fn new() -> HighestResponseRatioNext {
+            HighestResponseRatioNext {
+                stack: Vec::new(),
+                running: false,
+                chosen_ratio: None,
+            }
+        }
+    }
+
+    impl Cpu for HighestResponseRatioNext {
+        /// Highest Response Ratio Next (non-preemptive) algorithm
+        ///
+        /// Once the running process is finished, the process with the highest
+        /// (waiting + burst) / burst ratio is picked, ties are resolved by arrival, then PID
+        ///
+        /// # Arguments
+        /// * `arrival` - Vec<Process> - Processes to be added to the stack
+        /// * `timer` - u32 - Current timer state
+        ///
+        /// # Returns
+        /// * (new_timer, Option<pid>) - (u32, Option<u32>) - New timer state and PID of the process, if the process was finished (in previous loop)
+        fn next_loop(&mut self, arrival: Vec<Process>, timer: u32) -> (u32, Option<u32>) {
+            let mut pid = None;
+            if let Some(process) = self.stack.first() {
+                if process.burst == 0 {
+                    let process = self.stack.remove(0);
+                    self.running = false;
+                    self.chosen_ratio = None;
+                    pid = Some(process.pid);
+                }
+            }
+
+            if !self.running && !self.stack.is_empty() {
+                let (index, ratio) = self
+                    .stack
+                    .iter()
+                    .enumerate()
+                    .map(|(i, x)| (i, response_ratio(x, timer)))
+                    .fold(None, |best: Option<(usize, f64)>, (i, ratio)| match best {
+                        // Stack is ordered by arrival, so the first of equal ratios wins
+                        Some((_, best_ratio)) if ratio <= best_ratio => best,
+                        _ => Some((i, ratio)),
+                    })
+                    .unwrap();
+                let process = self.stack.remove(index);
+                self.stack.insert(0, process);
+                self.running = true;
+                self.chosen_ratio = Some(ratio);
+            }
+
+            if let Some(process) = self.stack.first_mut() {
+                if process.burst > 0 {
+                    process.burst -= 1;
+                }
+            }
+            self.stack.append(&mut arrival.clone());
+            (timer + 1, pid)
+        }
+
+        fn get_stack(&self) -> &Vec<Process> {
+            &self.stack
+        }
+
+        fn quiet_loops(&self) -> u32 {
+            // Next process is picked in the loop after the running one was finished
+            if self.running || self.stack.is_empty() {
+                quiet_front(&self.stack)
+            } else {
+                0
+            }
+        }
+
+        fn skip_loops(&mut self, loops: u32) {
+            run_front(&mut self.stack, loops);
+        }
+
+        fn table_header(&self) -> String {
+            format!("{};Response Ratio", process_table_header())
+        }
+
+        fn table(&self, time: &u32) -> Vec<String> {
+            process_table(&self.stack, time)
+                .into_iter()
+                .zip(self.stack.iter())
+                .enumerate()
+                .map(|(i, (row, process))| match (i, self.chosen_ratio) {
+                    (0, Some(ratio)) => format!("{};{:.2}", row, ratio),
+                    _ => format!("{};{:.2}", row, response_ratio(process, *time)),
+                })
+                .collect()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn process(pid: u32, burst: u32, priority: u32) -> Process {
+            Process {
+                pid,
+                burst,
+                priority,
+                ..Default::default()
+            }
+        }
+
+        fn priority(scheduler: &PriorityScheduler, pid: u32) -> u32 {
+            scheduler
+                .stack
+                .iter()
+                .find(|x| x.pid == pid)
+                .unwrap()
+                .priority
+        }
+
+        #[test]
+        fn aging_restarts_when_the_process_waits_again() {
+            let aging = Aging {
+                step: 1,
+                interval: 3,
+            };
+            let mut scheduler = PriorityScheduler::new(true, Some(aging));
+            let (mut timer, _) = scheduler.next_loop(vec![process(1, 2, 1), process(2, 5, 2)], 0);
+            // Process 2 waits two ticks for process 1, then runs once it's finished
+            for _ in 0..3 {
+                (timer, _) = scheduler.next_loop(Vec::new(), timer);
+            }
+            assert_eq!(scheduler.stack[0].pid, 2);
+            // Preempted by a higher priority arrival, it has to wait three whole ticks again
+            (timer, _) = scheduler.next_loop(vec![process(3, 5, 0)], timer);
+            for _ in 0..2 {
+                (timer, _) = scheduler.next_loop(Vec::new(), timer);
+                assert_eq!(priority(&scheduler, 2), 2);
+            }
+            scheduler.next_loop(Vec::new(), timer);
+            assert_eq!(priority(&scheduler, 2), 1);
+        }
     }
 }
@@ -1,6 +1,9 @@
-use crate::{cpu_scheduler::scheduler::Process, pager_gen};
-use rand::thread_rng;
+use crate::{
+    cpu_scheduler::scheduler::{PeriodicTask, Process},
+    pager_gen,
+};
 use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
 use rand_distr::{Distribution, Normal};
 
 pub fn low_burst_with_spikes(n: i32) -> Vec<Process> {
@@ -13,12 +16,14 @@ pub fn low_burst_with_spikes(n: i32) -> Vec<Process> {
                 pid: i as u32,
                 arrival: i as u32,
                 burst: 25,
+                ..Default::default()
             });
         } else {
             processes.push(Process {
                 pid: i as u32,
                 arrival: i as u32,
                 burst: normal.sample(&mut rng) as u32,
+                ..Default::default()
             });
         }
     }
@@ -33,17 +38,133 @@ pub fn high_burst_first_then_low(n: i32) -> Vec<Process> {
         pid: 0,
         arrival: 0,
         burst: 100,
+        ..Default::default()
     });
     for i in 1..n {
         processes.push(Process {
             pid: i as u32,
             arrival: i as u32,
             burst: normal.sample(&mut rng) as u32,
+            ..Default::default()
         });
     }
     processes
 }
 
+pub fn high_priority_stream(n: i32) -> Vec<Process> {
+    let mut processes = Vec::new();
+    let mut rng = thread_rng();
+    let normal = Normal::new(3.0, 1.0).expect("Invalid parameters");
+    processes.push(Process {
+        pid: 0,
+        arrival: 0,
+        burst: 10,
+        priority: 10,
+        ..Default::default()
+    });
+    // Stream of high priority processes arriving faster than they can be served
+    // (should show the starving problem in priority scheduling without aging)
+    for i in 1..n {
+        processes.push(Process {
+            pid: i as u32,
+            arrival: (i as u32) * 2,
+            burst: normal.sample(&mut rng) as u32,
+            priority: rng.gen_range(0..3),
+            ..Default::default()
+        });
+    }
+    processes
+}
+
+pub fn mixed_nice_values(n: i32) -> Vec<Process> {
+    let mut processes = Vec::new();
+    let mut rng = thread_rng();
+    let normal = Normal::new(20.0, 5.0).expect("Invalid parameters");
+    for i in 0..n {
+        processes.push(Process {
+            pid: i as u32,
+            arrival: i as u32,
+            burst: normal.sample(&mut rng) as u32,
+            nice: rng.gen_range(-20..20),
+            ..Default::default()
+        });
+    }
+    processes
+}
+
+pub fn mixed_ticket_counts(n: i32) -> Vec<Process> {
+    let mut processes = Vec::new();
+    let mut rng = thread_rng();
+    let normal = Normal::new(20.0, 5.0).expect("Invalid parameters");
+    for i in 0..n {
+        processes.push(Process {
+            pid: i as u32,
+            arrival: i as u32,
+            burst: normal.sample(&mut rng) as u32,
+            tickets: [1, 10, 100][rng.gen_range(0..3)],
+            ..Default::default()
+        });
+    }
+    processes
+}
+
+pub fn io_and_cpu_bound(n: i32) -> Vec<(Process, Vec<u32>)> {
+    // Even PIDs are I/O-bound (short CPU bursts, long I/O), odd PIDs are CPU-bound
+    let mut processes = Vec::new();
+    let mut rng = thread_rng();
+    for i in 0..n {
+        let sequence = if i % 2 == 0 {
+            let mut sequence = vec![rng.gen_range(1..=3)];
+            for _ in 0..4 {
+                sequence.push(rng.gen_range(5..=10));
+                sequence.push(rng.gen_range(1..=3));
+            }
+            sequence
+        } else {
+            vec![
+                rng.gen_range(15..=25),
+                rng.gen_range(1..=2),
+                rng.gen_range(15..=25),
+            ]
+        };
+        let process = Process {
+            pid: i as u32,
+            arrival: i as u32 * 4,
+            burst: sequence[0],
+            ..Default::default()
+        };
+        processes.push((process, sequence));
+    }
+    processes
+}
+
+pub fn periodic_tasks(n: usize, utilization: f64) -> Vec<PeriodicTask> {
+    // Utilization is split between tasks with UUniFast algorithm
+    // https://doi.org/10.1007/s11241-005-0507-9
+    let mut rng = thread_rng();
+    let periods = [5, 10, 20, 40];
+    let mut remaining = utilization;
+    let mut tasks = Vec::new();
+    for i in 0..n {
+        let task_utilization = if i == n - 1 {
+            remaining
+        } else {
+            let next = remaining * rng.gen::<f64>().powf(1.0 / (n - i - 1) as f64);
+            let task_utilization = remaining - next;
+            remaining = next;
+            task_utilization
+        };
+        let period = periods[rng.gen_range(0..periods.len())];
+        tasks.push(PeriodicTask {
+            id: i as u32,
+            period,
+            wcet: ((task_utilization * period as f64).round() as u32).max(1),
+            deadline: period,
+        });
+    }
+    tasks
+}
+
 pub fn belady_anomaly(n: usize) -> Vec<u32> {
     // Known sequnce of page numbers that causes the Belady's anomaly
     // https://en.wikipedia.org/wiki/B%C3%A9l%C3%A1dy%27s_anomaly
@@ -57,6 +178,32 @@ pub fn repeating_pages_sequence(seq: &[u32], n: usize) -> Vec<u32> {
     pages.to_vec()
 }
 
+/// Random prefix followed by a repeating sequence of five pages, the prefix leaves
+/// page frequencies behind that Least Frequently Used has to recover from
+///
+/// # Arguments
+/// * `rng` - &mut impl Rng - Source of the random prefix, seed it for a reproducible sequence
+///
+/// # Returns
+/// * Vec<u32> - Reference string
+/// * usize - Length of the random prefix
+pub fn lfu_recovery_sequence(rng: &mut impl Rng) -> (Vec<u32>, usize) {
+    let mut extended_sequence =
+        pager_gen::paging_data_generator::generate_page_numbers_from(rng, 100, 3.0, 2.0);
+    let prefix = extended_sequence.len();
+    extended_sequence.extend(repeating_pages_sequence(&[1, 2, 3, 4, 5], 400));
+    (extended_sequence, prefix)
+}
+
+/// Random page references, each of them is a write with the given probability
+pub fn pages_with_writes(n: usize, avg: f64, stdev: f64, write_ratio: f64) -> Vec<(u32, bool)> {
+    let mut rng = thread_rng();
+    pager_gen::paging_data_generator::generate_page_numbers(n, avg, stdev)
+        .into_iter()
+        .map(|page| (page, rng.gen_bool(write_ratio)))
+        .collect()
+}
+
 pub fn frequent_page(n: usize, dupes: usize, avg: f64, stdev: f64) -> Vec<u32> {
     let mut pages = pager_gen::paging_data_generator::generate_page_numbers(n - dupes, avg, stdev);
     pages.extend(vec![0; dupes]);
@@ -0,0 +1,246 @@
+pub mod events {
+    use std::collections::HashMap;
+
+    /// Change of the state of a single process
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub enum EventKind {
+        /// Process became ready, either released or back from I/O
+        Arrived,
+        Dispatched,
+        /// Process lost the CPU before finishing its burst
+        Preempted,
+        /// Process finished its CPU burst and started waiting for I/O
+        Blocked,
+        Completed,
+    }
+
+    #[derive(Copy, Clone, Debug)]
+    pub struct LogEntry {
+        pub time: u32,
+        pub pid: u32,
+        pub kind: EventKind,
+    }
+
+    /// Log of a single core simulation
+    ///
+    /// Loop at timer `t` simulates the tick ending at `t`, so a process arriving at `t`
+    /// (delivered at the end of that loop) is dispatched at `t` at the earliest
+    #[derive(Default)]
+    pub struct EventLog {
+        pub entries: Vec<LogEntry>,
+        /// PID of the process holding the CPU and the loop it last ran in
+        on_cpu: Option<(u32, u32)>,
+    }
+
+    impl EventLog {
+        fn push(&mut self, time: u32, pid: u32, kind: EventKind) {
+            self.entries.push(LogEntry { time, pid, kind });
+        }
+
+        pub fn arrived(&mut self, pid: u32, time: u32) {
+            self.push(time, pid, EventKind::Arrived);
+        }
+
+        /// Record the process running in the loops `first..=last`, preempting the process
+        /// which held the CPU before, if any
+        pub fn ran(&mut self, pid: u32, first: u32, last: u32) {
+            match self.on_cpu {
+                Some((running, _)) if running == pid => {}
+                Some((running, end)) => {
+                    self.push(end, running, EventKind::Preempted);
+                    self.push(first - 1, pid, EventKind::Dispatched);
+                }
+                None => self.push(first - 1, pid, EventKind::Dispatched),
+            }
+            self.on_cpu = Some((pid, last));
+        }
+
+        /// Record the process holding the CPU finishing its burst
+        ///
+        /// # Arguments
+        /// * `pid` - u32 - PID of the finished process
+        /// * `blocked` - bool - Whether the process continues with an I/O burst
+        ///
+        /// # Returns
+        /// * u32 - Time the burst was finished at
+        pub fn finished(&mut self, pid: u32, blocked: bool) -> u32 {
+            let (running, end) = self
+                .on_cpu
+                .take()
+                .filter(|(running, _)| *running == pid)
+                .unwrap_or_else(|| panic!("Process {} finished without holding the CPU", pid));
+            let kind = match blocked {
+                true => EventKind::Blocked,
+                false => EventKind::Completed,
+            };
+            self.push(end, running, kind);
+            end
+        }
+
+        /// Record the process finishing an empty CPU burst right as it arrived
+        pub fn finished_on_arrival(&mut self, pid: u32, time: u32, blocked: bool) {
+            let kind = match blocked {
+                true => EventKind::Blocked,
+                false => EventKind::Completed,
+            };
+            self.push(time, pid, kind);
+        }
+
+        /// Times of every completed process, in order of completion
+        pub fn process_times(&self) -> Vec<ProcessTimes> {
+            let mut states: HashMap<u32, (ProcessTimes, u32)> = HashMap::new();
+            let mut times = Vec::new();
+            for entry in self.entries.iter() {
+                let (process, since) = states.entry(entry.pid).or_insert((
+                    ProcessTimes {
+                        pid: entry.pid,
+                        arrival: entry.time,
+                        ..Default::default()
+                    },
+                    entry.time,
+                ));
+                let elapsed = entry.time - *since;
+                match entry.kind {
+                    // Process is Blocked until it arrives again
+                    EventKind::Arrived => process.blocked += elapsed,
+                    EventKind::Dispatched => {
+                        process.waiting += elapsed;
+                        process.response.get_or_insert(entry.time - process.arrival);
+                    }
+                    EventKind::Preempted | EventKind::Blocked => process.cpu += elapsed,
+                    EventKind::Completed => {
+                        process.cpu += elapsed;
+                        process.completion = entry.time;
+                        times.push(*process);
+                    }
+                }
+                *since = entry.time;
+            }
+            times
+        }
+    }
+
+    /// Times of a single process derived from the event log
+    #[derive(Copy, Clone, Debug, Default)]
+    pub struct ProcessTimes {
+        pub pid: u32,
+        pub arrival: u32,
+        pub completion: u32,
+        /// Time spent running
+        pub cpu: u32,
+        /// Time spent in the Ready state
+        pub waiting: u32,
+        /// Time spent in the Blocked state, waiting for and using the I/O device
+        pub blocked: u32,
+        /// Time from arrival to the first dispatch
+        pub response: Option<u32>,
+    }
+
+    impl ProcessTimes {
+        pub fn turnaround(&self) -> u32 {
+            self.completion - self.arrival
+        }
+    }
+
+    /// Check that the log is a valid sequence of state changes, with at most one process
+    /// holding the CPU, that every process ran exactly for its burst and that running,
+    /// waiting and blocked time add up to its turnaround
+    ///
+    /// # Arguments
+    /// * `log` - &EventLog - Log of the simulation
+    /// * `bursts` - &HashMap<u32, u32> - Total CPU burst of every process, by PID
+    ///
+    /// # Returns
+    /// * Result<(), String> - Description of the first inconsistency found
+    pub fn check_consistency(log: &EventLog, bursts: &HashMap<u32, u32>) -> Result<(), String> {
+        let mut last: HashMap<u32, LogEntry> = HashMap::new();
+        let mut holder: Option<u32> = None;
+        for entry in log.entries.iter() {
+            let previous = last.get(&entry.pid).map(|x| x.kind);
+            let valid = match entry.kind {
+                EventKind::Arrived => matches!(previous, None | Some(EventKind::Blocked)),
+                EventKind::Dispatched => matches!(
+                    previous,
+                    Some(EventKind::Arrived) | Some(EventKind::Preempted)
+                ),
+                EventKind::Preempted => previous == Some(EventKind::Dispatched),
+                // Empty CPU burst is finished as soon as the process arrives
+                EventKind::Blocked | EventKind::Completed => match last.get(&entry.pid) {
+                    Some(x) if x.kind == EventKind::Arrived => x.time == entry.time,
+                    Some(x) => x.kind == EventKind::Dispatched,
+                    None => false,
+                },
+            };
+            if !valid {
+                return Err(format!(
+                    "PID {}: {:?} after {:?} at {}",
+                    entry.pid, entry.kind, previous, entry.time
+                ));
+            }
+            if last.get(&entry.pid).is_some_and(|x| x.time > entry.time) {
+                return Err(format!(
+                    "PID {}: {:?} at {} goes back in time",
+                    entry.pid, entry.kind, entry.time
+                ));
+            }
+            match entry.kind {
+                EventKind::Dispatched => {
+                    if let Some(running) = holder {
+                        return Err(format!(
+                            "PID {} dispatched at {} while PID {} is running",
+                            entry.pid, entry.time, running
+                        ));
+                    }
+                    holder = Some(entry.pid);
+                }
+                EventKind::Preempted | EventKind::Blocked | EventKind::Completed => {
+                    if holder == Some(entry.pid) {
+                        holder = None;
+                    }
+                }
+                EventKind::Arrived => {}
+            }
+            last.insert(entry.pid, *entry);
+        }
+
+        let times = log.process_times();
+        if times.len() != bursts.len() {
+            return Err(format!(
+                "{} of {} processes completed",
+                times.len(),
+                bursts.len()
+            ));
+        }
+        for process in times.iter() {
+            let burst = bursts
+                .get(&process.pid)
+                .ok_or_else(|| format!("PID {} was never queued", process.pid))?;
+            if process.cpu != *burst {
+                return Err(format!(
+                    "PID {} ran for {} instead of {}",
+                    process.pid, process.cpu, burst
+                ));
+            }
+            if process.cpu + process.waiting + process.blocked != process.turnaround() {
+                return Err(format!(
+                    "PID {}: burst {} + waiting {} + blocked {} != turnaround {}",
+                    process.pid,
+                    process.cpu,
+                    process.waiting,
+                    process.blocked,
+                    process.turnaround()
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the log as CSV rows of time, PID and event
+    pub fn to_csv(log: &[LogEntry]) -> String {
+        let mut result = String::from("Time;PID;Event\n");
+        for entry in log.iter() {
+            result.push_str(&format!("{};{};{:?}\n", entry.time, entry.pid, entry.kind));
+        }
+        result
+    }
+}
@@ -0,0 +1,175 @@
+pub mod gantt {
+    /// State of the CPU during a single tick
+    #[derive(Copy, Clone, Debug, PartialEq)]
+    pub enum Slot {
+        Idle,
+        Switch,
+        Run(u32),
+    }
+
+    /// Consecutive ticks with the same state
+    #[derive(Copy, Clone, Debug)]
+    pub struct Segment {
+        pub slot: Slot,
+        pub start: usize,
+        pub length: usize,
+    }
+
+    /// State of the CPU over time, stored as segments of consecutive equal slots,
+    /// so a stretch of any length takes a single segment
+    #[derive(Clone, Debug, Default)]
+    pub struct Timeline {
+        pub segments: Vec<Segment>,
+    }
+
+    impl Timeline {
+        /// Number of ticks covered by the timeline
+        pub fn ticks(&self) -> usize {
+            self.segments.last().map_or(0, |x| x.start + x.length)
+        }
+
+        pub fn last(&self) -> Option<Slot> {
+            self.segments.last().map(|x| x.slot)
+        }
+
+        /// Append `length` ticks in the given state
+        pub fn push(&mut self, slot: Slot, length: usize) {
+            if length == 0 {
+                return;
+            }
+            let start = self.ticks();
+            match self.segments.last_mut() {
+                Some(segment) if segment.slot == slot => segment.length += length,
+                _ => self.segments.push(Segment {
+                    slot,
+                    start,
+                    length,
+                }),
+            }
+        }
+
+        /// Remove the first tick, the rest of the timeline starts a tick earlier
+        pub fn pop_front(&mut self) {
+            if let Some(first) = self.segments.first_mut() {
+                first.length -= 1;
+                if first.length == 0 {
+                    self.segments.remove(0);
+                }
+                for segment in self.segments.iter_mut().filter(|x| x.start > 0) {
+                    segment.start -= 1;
+                }
+            }
+        }
+
+        /// Remove the last tick
+        pub fn pop(&mut self) {
+            if let Some(last) = self.segments.last_mut() {
+                last.length -= 1;
+                if last.length == 0 {
+                    self.segments.pop();
+                }
+            }
+        }
+    }
+
+    fn label(slot: &Slot) -> String {
+        match slot {
+            Slot::Idle => "--".to_string(),
+            Slot::Switch => "cs".to_string(),
+            Slot::Run(pid) => format!("P{}", pid),
+        }
+    }
+
+    /// Render the timeline as two lines, segments with their lengths and start times below them
+    ///
+    /// # Example
+    /// ```text
+    /// |--(1)|P0(4)|cs(1)|P1(2)|
+    /// 0     1     5     6     8
+    /// ```
+    pub fn to_ascii(timeline: &Timeline) -> String {
+        let mut bars = String::from("|");
+        let mut times = String::new();
+        for segment in timeline.segments.iter() {
+            let cell = format!("{}({})", label(&segment.slot), segment.length);
+            times.push_str(&format!(
+                "{:<width$}",
+                segment.start,
+                width = cell.len() + 1
+            ));
+            bars.push_str(&cell);
+            bars.push('|');
+        }
+        times.push_str(&timeline.ticks().to_string());
+        format!("{}\n{}", bars, times)
+    }
+
+    /// Render the timeline as a standalone SVG document
+    ///
+    /// Processes are drawn as blue bars labeled with their PID (when wide enough),
+    /// idle periods as grey and context switches as red bars
+    pub fn to_svg(timeline: &Timeline, title: &str) -> String {
+        let width = 1200.0;
+        let scale = width / timeline.ticks().max(1) as f64;
+        let (left, top, height) = (10.0, 30.0, 40.0);
+        let mut svg = String::new();
+        svg.push_str(&format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{}\" height=\"{}\" font-family=\"monospace\" font-size=\"10\">\n",
+            width + 2.0 * left,
+            top + height + 30.0
+        ));
+        svg.push_str(&format!(
+            "<text x=\"{}\" y=\"18\" font-size=\"14\">{}</text>\n",
+            left, title
+        ));
+        for segment in timeline.segments.iter() {
+            let x = left + segment.start as f64 * scale;
+            let w = segment.length as f64 * scale;
+            let fill = match segment.slot {
+                Slot::Idle => "#d0d0d0",
+                Slot::Switch => "#e04040",
+                Slot::Run(_) => "#4a7fd0",
+            };
+            svg.push_str(&format!(
+                "<rect x=\"{:.2}\" y=\"{}\" width=\"{:.2}\" height=\"{}\" fill=\"{}\" stroke=\"#ffffff\" stroke-width=\"0.5\"><title>{} {}..{}</title></rect>\n",
+                x,
+                top,
+                w,
+                height,
+                fill,
+                label(&segment.slot),
+                segment.start,
+                segment.start + segment.length
+            ));
+            let text = label(&segment.slot);
+            // Roughly 6px per character at font size 10
+            if let Slot::Run(_) = segment.slot {
+                if w >= text.len() as f64 * 6.0 + 2.0 {
+                    svg.push_str(&format!(
+                        "<text x=\"{:.2}\" y=\"{}\" fill=\"#ffffff\">{}</text>\n",
+                        x + 1.0,
+                        top + height / 2.0 + 4.0,
+                        text
+                    ));
+                }
+            }
+        }
+        // Time axis with ~10 ticks
+        let step = (timeline.ticks() / 10).max(1);
+        for time in (0..=timeline.ticks()).step_by(step) {
+            let x = left + time as f64 * scale;
+            svg.push_str(&format!(
+                "<line x1=\"{:.2}\" y1=\"{}\" x2=\"{:.2}\" y2=\"{}\" stroke=\"#000000\"/>\n<text x=\"{:.2}\" y=\"{}\">{}</text>\n",
+                x,
+                top + height,
+                x,
+                top + height + 5.0,
+                x,
+                top + height + 17.0,
+                time
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
@@ -1,23 +1,59 @@
-use cpu_pager::paging::{FirstInFirstOut, LeastFrequentlyUsed};
-use cpu_scheduler::scheduler::{FirstComeFirstServe, RoundRobin};
+use cpu_multicore::multicore::{MultiCore, QueueMode};
+use cpu_pager::paging::{
+    AdaptiveReplacementCache, AgingLeastRecentlyUsed, Clock, ClockPro, EnhancedSecondChance,
+    FirstInFirstOut, LeastFrequentlyUsed, LeastRecentlyUsed, LowInterReferenceRecencySet,
+    OptimalReplacement, PageFaultFrequency, PageFaultFrequencyConfig, PagingAlgorithm, TwoQueue,
+    WorkingSet, WorkingSetClock,
+};
+use cpu_scheduler::scheduler::{
+    hyperperiod, Aging, CompletelyFairScheduler, EarliestDeadlineFirst, FirstComeFirstServe,
+    HighestResponseRatioNext, LotteryScheduler, MultilevelFeedbackQueue,
+    MultilevelFeedbackQueueConfig, PriorityScheduler, RateMonotonic, RoundRobin, ShortestJobFirst,
+    ShortestRemainingTimeFirst, StrideScheduler, TieBreak,
+};
 // use scheduler_gen::scheduler_data_generator::Feeder;
 use std::fs;
+mod cpu_multicore;
 mod cpu_pager;
 mod cpu_scheduler;
 mod custom_gen;
+mod event_log;
+mod gantt_chart;
 mod pager_gen;
+mod paging_trace;
 mod scheduler_gen;
 
 static DEBUG: bool = false;
 static GENERATE_NEW_DATA: bool = false;
 static LOAD_EXISTING_DATA: bool = true;
+/// Ticks lost on every change of the running process in single core simulations
+static CONTEXT_SWITCH_COST: u32 = 1;
+/// Skip over quiet stretches of the simulation instead of running it tick by tick
+static EVENT_DRIVEN: bool = true;
+/// References between two shifts of the aging counters of the LRU approximation
+static PAGING_AGING_INTERVAL: u32 = 4;
+/// References spanned by the working set of Working Set algorithms and the size report
+static PAGING_WORKING_SET_WINDOW: u32 = 10;
+/// References summarized by a single value of the working set size timeline
+static PAGING_WORKING_SET_INTERVAL: usize = 50;
+/// Number of references the fault rate of Page Fault Frequency is measured over
+static PAGING_PFF_WINDOW: usize = 20;
+/// Seed of the random search for Belady's anomaly counterexamples
+static PAGING_BELADY_SEED: u64 = 42;
+/// Largest number of page frames the page replacement algorithms are compared with
+static PAGING_MAX_FRAMES: usize = 8;
+/// Upper limit of the simulated time for real-time task sets with long hyperperiods
+static REALTIME_HORIZON_LIMIT: u32 = 1000;
 
 fn main() {
     test_main();
-    let mut data = vec![
+    let data = vec![
         "123", "234", "345", "456", "567", "678", "789", "890", "901", "012",
     ];
-    let bind = data.iter().map(|&x| x.chars().rev().collect::<String>()).collect::<Vec<String>>();
+    let bind = data
+        .iter()
+        .map(|&x| x.chars().rev().collect::<String>())
+        .collect::<Vec<String>>();
     let output = bind.join("\n");
     println!("{}", output);
 }
@@ -32,7 +68,7 @@ fn test_main() {
         feeders.append(&mut import_scheduler_data("./tests/scheduler"));
     }
     let mut outputs = Vec::new();
-    for feeder in feeders {
+    for (i, mut feeder) in feeders.into_iter().enumerate() {
         println!("=========================================");
         println!("======= CPU scheduling algorithms =======");
         println!("Executing test cases with following data:");
@@ -40,8 +76,33 @@ fn test_main() {
             "Test data:\n{}",
             scheduler_gen::scheduler_data_generator::parse_test_data(&feeder.processes)
         );
-        outputs.push(execute_scheduler_feeder(feeder));
-        export_scheduler_outputs(&outputs);
+        outputs.push(execute_scheduler_feeder(&mut feeder));
+        export_scheduler_outputs(&outputs, "scheduler");
+        export_gantt_charts(&feeder, "scheduler", i);
+        export_event_logs(&feeder, "scheduler", i);
+        export_summaries(&feeder, "scheduler", i);
+        println!("=========================================");
+    }
+
+    let mut feeders: Vec<scheduler_gen::scheduler_data_generator::Feeder> = Vec::new();
+    if GENERATE_NEW_DATA {
+        feeders.append(&mut gen_realtime_data());
+        export_realtime_data(&feeders);
+    }
+    if LOAD_EXISTING_DATA {
+        feeders.append(&mut import_realtime_data("./tests/realtime"));
+    }
+    let mut outputs = Vec::new();
+    for (i, mut feeder) in feeders.into_iter().enumerate() {
+        println!("=========================================");
+        println!("====== Real-time scheduling algorithms ======");
+        println!("Executing test cases with following tasks:");
+        println!("{:?}", &feeder.tasks);
+        outputs.push(execute_realtime_feeder(&mut feeder));
+        export_scheduler_outputs(&outputs, "realtime");
+        export_gantt_charts(&feeder, "realtime", i);
+        export_event_logs(&feeder, "realtime", i);
+        export_summaries(&feeder, "realtime", i);
         println!("=========================================");
     }
 
@@ -52,32 +113,131 @@ fn test_main() {
             let feeder = pager_gen::paging_data_generator::Feeder::new(page_set);
             feeders.push(feeder);
         }
+        // Reads mixed with writes, for algorithms tracking dirty pages
+        feeders.push(pager_gen::paging_data_generator::Feeder::from_references(
+            custom_gen::pages_with_writes(500, 6.0, 3.0, 0.3),
+        ));
 
         export_paging_data(&feeders);
     }
     if LOAD_EXISTING_DATA {
         feeders.append(&mut import_paging_data("./tests/paging"));
     }
-    for feeder in feeders {
+    for (i, mut feeder) in feeders.into_iter().enumerate() {
         println!("=========================================");
         println!("====== Page replacement algorithms ======");
         println!("Executing test cases with following data:");
         println!("{:?}", &feeder.pages);
-        execute_paging_feeder(feeder);
+        execute_paging_feeder(&mut feeder);
+        export_paging_traces(&feeder, i);
     }
+    println!("=========================================");
+    println!("=== Belady's anomaly counterexamples ===");
+    search_belady_counterexamples();
+    println!("=========================================");
 }
 
 fn execute_scheduler_feeder(
-    mut feeder: scheduler_gen::scheduler_data_generator::Feeder,
+    feeder: &mut scheduler_gen::scheduler_data_generator::Feeder,
 ) -> Vec<String> {
     // let mut feeder = Feeder::new(5, 0, 10, 5.0, 1.0);
-    println!("Algorihms: FirstComeFirstServe, RoundRobin(2), RoundRobin(5)");
+    println!("Algorihms: FirstComeFirstServe, RoundRobin(2), RoundRobin(5), ShortestJobFirst(Arrival), ShortestJobFirst(Pid), ShortestRemainingTimeFirst, Priority, Priority(Aging), PreemptivePriority, PreemptivePriority(Aging), MultilevelFeedbackQueue([2, 4, 8], 50), MultilevelFeedbackQueue([1, 2, 4, 8], None), CompletelyFairScheduler(24, 3), LotteryScheduler(2), StrideScheduler(2), HighestResponseRatioNext");
+    println!("Multi-core: MultiCore(4, Global), MultiCore(4, PerCore with load balancing), MultiCore(4, PerCore with work stealing), all with RoundRobin(2)");
+    let aging = Aging {
+        step: 1,
+        interval: 5,
+    };
+    feeder.context_switch_cost = CONTEXT_SWITCH_COST;
+    feeder.event_driven = EVENT_DRIVEN;
     feeder.add_function(Box::new(FirstComeFirstServe::new()));
     feeder.add_function(Box::new(RoundRobin::new(2)));
     feeder.add_function(Box::new(RoundRobin::new(5)));
+    feeder.add_function(Box::new(ShortestJobFirst::new(TieBreak::Arrival)));
+    feeder.add_function(Box::new(ShortestJobFirst::new(TieBreak::Pid)));
+    feeder.add_function(Box::new(ShortestRemainingTimeFirst::new(TieBreak::Arrival)));
+    feeder.add_function(Box::new(PriorityScheduler::new(false, None)));
+    feeder.add_function(Box::new(PriorityScheduler::new(false, Some(aging))));
+    feeder.add_function(Box::new(PriorityScheduler::new(true, None)));
+    feeder.add_function(Box::new(PriorityScheduler::new(true, Some(aging))));
+    let mlfq_configs = vec![
+        MultilevelFeedbackQueueConfig {
+            quanta: vec![2, 4, 8],
+            boost_period: Some(50),
+        },
+        MultilevelFeedbackQueueConfig {
+            quanta: vec![1, 2, 4, 8],
+            boost_period: None,
+        },
+    ];
+    for config in mlfq_configs {
+        feeder.add_function(Box::new(MultilevelFeedbackQueue::new(config)));
+    }
+    feeder.add_function(Box::new(CompletelyFairScheduler::new(24, 3)));
+    feeder.add_function(Box::new(LotteryScheduler::new(2, 42)));
+    feeder.add_function(Box::new(StrideScheduler::new(2)));
+    feeder.add_function(Box::new(HighestResponseRatioNext::new()));
+    feeder.add_multicore(MultiCore::new(4, QueueMode::Global, Some(2)));
+    feeder.add_multicore(MultiCore::new(
+        4,
+        QueueMode::PerCore {
+            balance_period: Some(10),
+            work_stealing: false,
+        },
+        Some(2),
+    ));
+    feeder.add_multicore(MultiCore::new(
+        4,
+        QueueMode::PerCore {
+            balance_period: None,
+            work_stealing: true,
+        },
+        Some(2),
+    ));
+    feeder.feed()
+}
+
+fn execute_realtime_feeder(
+    feeder: &mut scheduler_gen::scheduler_data_generator::Feeder,
+) -> Vec<String> {
+    println!(
+        "Algorithms: FirstComeFirstServe, RoundRobin(2), EarliestDeadlineFirst, RateMonotonic"
+    );
+    feeder.event_driven = EVENT_DRIVEN;
+    feeder.add_function(Box::new(FirstComeFirstServe::new()));
+    feeder.add_function(Box::new(RoundRobin::new(2)));
+    feeder.add_function(Box::new(EarliestDeadlineFirst::new()));
+    feeder.add_function(Box::new(RateMonotonic::new()));
     feeder.feed()
 }
 
+fn gen_realtime_data() -> Vec<scheduler_gen::scheduler_data_generator::Feeder> {
+    use scheduler_gen::scheduler_data_generator::Feeder;
+    vec![0.6, 0.8, 0.95, 1.1]
+        .into_iter()
+        .map(|utilization| {
+            let tasks = custom_gen::periodic_tasks(4, utilization);
+            let horizon = hyperperiod(&tasks).min(REALTIME_HORIZON_LIMIT);
+            Feeder::from_periodic_tasks(tasks, horizon)
+        })
+        .collect()
+}
+
+fn import_realtime_data(test_dir: &str) -> Vec<scheduler_gen::scheduler_data_generator::Feeder> {
+    use scheduler_gen::scheduler_data_generator::Feeder;
+    let mut feeders = Vec::new();
+    for file_name in find_files(test_dir) {
+        let feeder = Feeder::import_tasks_from_file(file_name, REALTIME_HORIZON_LIMIT);
+        feeders.push(feeder);
+    }
+    feeders
+}
+
+fn export_realtime_data(feeders: &[scheduler_gen::scheduler_data_generator::Feeder]) {
+    for (i, feeder) in feeders.iter().enumerate() {
+        feeder.export_tasks_to_file(format!("test_data_realtime_{i:02}.json").to_string());
+    }
+}
+
 fn gen_scheduler_data() -> Vec<scheduler_gen::scheduler_data_generator::Feeder> {
     use scheduler_gen::scheduler_data_generator::Feeder;
     vec![
@@ -87,6 +247,10 @@ fn gen_scheduler_data() -> Vec<scheduler_gen::scheduler_data_generator::Feeder>
         Feeder::new(100, 0, 100, 20.0, 5.0), // Different arrival times, different high burst times (low differences in burst times)
         Feeder::from(custom_gen::low_burst_with_spikes(100)), // Low burst times with spikes (should show the starving problem in FCFS)
         Feeder::from(custom_gen::high_burst_first_then_low(100)), // Single high burst time, rest low burst times (should show the starving problem in FCFS)
+        Feeder::from(custom_gen::high_priority_stream(100)), // Low priority process flooded by high priority ones (should show the starving problem in Priority without aging)
+        Feeder::from(custom_gen::mixed_nice_values(100)), // Random nice values (should show weighted CPU shares in CFS)
+        Feeder::from(custom_gen::mixed_ticket_counts(100)), // Random ticket counts (should show proportional shares in Lottery and Stride)
+        Feeder::from_burst_sequences(custom_gen::io_and_cpu_bound(100)), // Alternating CPU and I/O bursts (should show I/O-bound vs CPU-bound fairness)
     ]
 }
 
@@ -117,17 +281,74 @@ fn export_scheduler_data(feeders: &[scheduler_gen::scheduler_data_generator::Fee
     }
 }
 
-fn export_scheduler_outputs(outputs: &[Vec<String>]) {
+fn export_scheduler_outputs(outputs: &[Vec<String>], kind: &str) {
     for (i, output) in outputs.iter().enumerate() {
         let mut output_str = String::new();
         for line in output {
             output_str.push_str(line);
             output_str.push('\n');
         }
-        fs::write(format!("output_scheduler_{i:02}.csv"), output_str).unwrap();
+        fs::write(format!("output_{kind}_{i:02}.csv"), output_str).unwrap();
     }
 }
 
+fn export_gantt_charts(
+    feeder: &scheduler_gen::scheduler_data_generator::Feeder,
+    kind: &str,
+    dataset: usize,
+) {
+    use gantt_chart::gantt::{to_ascii, to_svg};
+    for (i, (name, timeline)) in feeder.timelines.iter().enumerate() {
+        println!("Gantt chart: {name}");
+        println!("{}", to_ascii(timeline));
+        let title = format!("{kind} {dataset:02}: {name}");
+        fs::write(
+            format!("gantt_{kind}_{dataset:02}_{i:02}_{name}.svg"),
+            to_svg(timeline, &title),
+        )
+        .unwrap();
+    }
+}
+
+fn export_event_logs(
+    feeder: &scheduler_gen::scheduler_data_generator::Feeder,
+    kind: &str,
+    dataset: usize,
+) {
+    use event_log::events::to_csv;
+    // All algorithms of the dataset in one file, like the scheduler outputs
+    let mut output = String::new();
+    for (name, log) in feeder.event_logs.iter() {
+        output.push_str(&format!("{name}\n{}\n", to_csv(log)));
+    }
+    fs::write(format!("events_{kind}_{dataset:02}.csv"), output).unwrap();
+}
+
+fn export_summaries(
+    feeder: &scheduler_gen::scheduler_data_generator::Feeder,
+    kind: &str,
+    dataset: usize,
+) {
+    fs::write(
+        format!("summary_{kind}_{dataset:02}.json"),
+        serde_json::to_string_pretty(&feeder.summaries).unwrap(),
+    )
+    .unwrap();
+}
+
+fn export_paging_traces(feeder: &pager_gen::paging_data_generator::Feeder, dataset: usize) {
+    use paging_trace::trace::{to_csv, to_markdown};
+    // All algorithms and frame counts of the dataset in one file, like the scheduler outputs
+    let (mut csv, mut markdown) = (String::new(), String::new());
+    for (name, trace) in feeder.traces.iter() {
+        csv.push_str(&format!("{name}\n{}\n", to_csv(trace)));
+        markdown.push_str(&format!("## {name}\n\n{}\n", to_markdown(trace)));
+    }
+    let filename = format!("trace_paging_{dataset:02}");
+    fs::write(format!("{filename}.csv"), csv).unwrap();
+    fs::write(format!("{filename}.md"), markdown).unwrap();
+}
+
 fn export_paging_data(feeders: &[pager_gen::paging_data_generator::Feeder]) {
     for (i, feeder) in feeders.iter().enumerate() {
         feeder.export_to_file(format!("test_data_paging_{i:02}.json").to_string());
@@ -143,27 +364,172 @@ fn import_paging_data(test_dir: &str) -> Vec<pager_gen::paging_data_generator::F
     feeders
 }
 
+/// Run the algorithms under Page Fault Frequency control and compare them with
+/// the runs with fixed number of frames
+fn execute_page_fault_frequency(feeder: &pager_gen::paging_data_generator::Feeder) {
+    let (min_frames, max_frames) = (2, 5);
+    let factories: Vec<fn(usize) -> Box<dyn PagingAlgorithm>> = vec![
+        |n| Box::new(FirstInFirstOut::new(n)),
+        |n| Box::new(LeastFrequentlyUsed::new(n)),
+        |n| Box::new(LeastRecentlyUsed::new(n)),
+        |n| Box::new(Clock::new(n)),
+    ];
+    for factory in factories {
+        let config = PageFaultFrequencyConfig {
+            initial_frames: min_frames,
+            min_frames,
+            max_frames,
+            window: PAGING_PFF_WINDOW,
+            lower: 0.1,
+            upper: 0.3,
+        };
+        let mut controller = PageFaultFrequency::new(config, factory);
+        let faults = feeder.fault_positions(&mut controller).len();
+        let fixed = (min_frames..=max_frames)
+            .map(|n| {
+                feeder
+                    .fault_positions(factory(n).as_mut())
+                    .len()
+                    .to_string()
+            })
+            .collect::<Vec<String>>();
+        // Allocation timeline as runs of the same number of frames
+        let mut runs: Vec<(usize, usize)> = Vec::new();
+        for frames in controller.timeline.iter() {
+            match runs.last_mut() {
+                Some((last, count)) if last == frames => *count += 1,
+                _ => runs.push((*frames, 1)),
+            }
+        }
+        let average = controller.timeline.iter().sum::<usize>() as f64
+            / controller.timeline.len().max(1) as f64;
+        println!(
+            "{}: Total page faults: {}, average frames {:.2}, fixed frames {}..={}: {}",
+            controller.name(),
+            faults,
+            average,
+            min_frames,
+            max_frames,
+            fixed.join(" ")
+        );
+        println!(
+            "Frame allocation (frames x references): {}",
+            runs.iter()
+                .map(|(frames, count)| format!("{frames}x{count}"))
+                .collect::<Vec<String>>()
+                .join(" ")
+        );
+    }
+}
+
+/// Algorithms with a fixed number of frames, which can be created for any of them
+fn paging_factories() -> Vec<fn(usize) -> Box<dyn PagingAlgorithm>> {
+    vec![
+        |n| Box::new(FirstInFirstOut::new(n)),
+        |n| Box::new(LeastFrequentlyUsed::new(n)),
+        |n| Box::new(LeastRecentlyUsed::new(n)),
+        |n| Box::new(AgingLeastRecentlyUsed::new(n, PAGING_AGING_INTERVAL)),
+        |n| Box::new(Clock::new(n)),
+        |n| Box::new(EnhancedSecondChance::new(n)),
+        |n| Box::new(AdaptiveReplacementCache::new(n)),
+        |n| Box::new(TwoQueue::new(n)),
+        |n| Box::new(LowInterReferenceRecencySet::new(n)),
+        |n| Box::new(ClockPro::new(n)),
+        |n| Box::new(WorkingSet::new(n, PAGING_WORKING_SET_WINDOW)),
+        |n| Box::new(WorkingSetClock::new(n, PAGING_WORKING_SET_WINDOW)),
+    ]
+}
+
+/// Flag every number of frames at which one more frame yields more Page Faults
+fn check_belady_anomalies(feeder: &pager_gen::paging_data_generator::Feeder) {
+    let mut anomalies = 0;
+    for factory in paging_factories() {
+        let name = factory(2).name();
+        for (n, faults, more_faults) in feeder.belady_anomalies(&factory, 2..=PAGING_MAX_FRAMES) {
+            println!(
+                "Belady's anomaly: {name} yields {faults} page faults with {n} frames and {more_faults} with {}",
+                n + 1
+            );
+            anomalies += 1;
+        }
+    }
+    if anomalies == 0 {
+        println!("Belady's anomaly: none for frames 2 to {PAGING_MAX_FRAMES}");
+    }
+}
+
+/// Search random reference strings for the smallest Belady's anomaly of every algorithm
+fn search_belady_counterexamples() {
+    use pager_gen::paging_data_generator::find_belady_counterexample;
+    let frames = 3;
+    for factory in paging_factories() {
+        let name = factory(frames).name();
+        match find_belady_counterexample(&factory, frames, 5, 20, 20000, PAGING_BELADY_SEED) {
+            Some(references) => println!(
+                "{name}: {} references with {frames} vs {} frames: {:?}",
+                references.len(),
+                frames + 1,
+                references
+            ),
+            None => println!("{name}: no counterexample found (seed {PAGING_BELADY_SEED})"),
+        }
+    }
+}
+
 fn gen_paging_data() -> Vec<Vec<u32>> {
     use pager_gen::paging_data_generator::generate_page_numbers;
-    let mut extended_sequence = generate_page_numbers(100, 3.0, 2.0);
-    extended_sequence.extend(custom_gen::repeating_pages_sequence(&[1, 2, 3, 4, 5], 400));
     vec![
         custom_gen::frequent_page(500, 200, 10.0, 3.0), // One page is repeated often
         custom_gen::belady_anomaly(50),                 // Known case of Belady's Anomaly, extended
         custom_gen::repeating_pages_sequence(&[1, 2, 3, 4, 5], 500), // Repeating sequence
-        extended_sequence, // Repeating sequence prepped with random numbers (to check for LFU recovery time)
+        custom_gen::lfu_recovery_sequence(&mut rand::thread_rng()).0, // Repeating sequence prepped with random numbers (to check for LFU recovery time)
         generate_page_numbers(500, 3.0, 2.0), // low amount of duplicates, completly random
         generate_page_numbers(500, 10.0, 5.0), // high amount of duplicates, completly random
     ]
 }
 
-fn execute_paging_feeder(mut feeder: pager_gen::paging_data_generator::Feeder) {
+fn execute_paging_feeder(feeder: &mut pager_gen::paging_data_generator::Feeder) {
     // Test with different page sizes to check for Belady's Anomaly
-    println!("Algorithms: FirstInFirstOut(n), LeastFrequentlyUsed(n), where n is in range 2 to 5");
-    for n in 2..=5 {
+    println!("Algorithms: FirstInFirstOut(n), LeastFrequentlyUsed(n), LeastRecentlyUsed(n), AgingLeastRecentlyUsed(n, {PAGING_AGING_INTERVAL}), OptimalReplacement(n), Clock(n), EnhancedSecondChance(n), AdaptiveReplacementCache(n), TwoQueue(n), LowInterReferenceRecencySet(n), ClockPro(n), WorkingSet(n, {PAGING_WORKING_SET_WINDOW}), WorkingSetClock(n, {PAGING_WORKING_SET_WINDOW}), where n is in range 2 to {PAGING_MAX_FRAMES}");
+    for n in 2..=PAGING_MAX_FRAMES {
         feeder.add_function(Box::new(FirstInFirstOut::new(n)));
         feeder.add_function(Box::new(LeastFrequentlyUsed::new(n)));
+        feeder.add_function(Box::new(LeastRecentlyUsed::new(n)));
+        feeder.add_function(Box::new(AgingLeastRecentlyUsed::new(
+            n,
+            PAGING_AGING_INTERVAL,
+        )));
+        feeder.add_function(Box::new(OptimalReplacement::new(n, feeder.pages.clone())));
+        feeder.add_function(Box::new(Clock::new(n)));
+        feeder.add_function(Box::new(EnhancedSecondChance::new(n)));
+        feeder.add_function(Box::new(AdaptiveReplacementCache::new(n)));
+        feeder.add_function(Box::new(TwoQueue::new(n)));
+        feeder.add_function(Box::new(LowInterReferenceRecencySet::new(n)));
+        feeder.add_function(Box::new(ClockPro::new(n)));
+        feeder.add_function(Box::new(WorkingSet::new(n, PAGING_WORKING_SET_WINDOW)));
+        feeder.add_function(Box::new(WorkingSetClock::new(n, PAGING_WORKING_SET_WINDOW)));
     }
     feeder.feed();
+    for n in 2..=PAGING_MAX_FRAMES {
+        println!(
+            "Frames {n}: {}",
+            feeder.approximation_error(
+                Box::new(LeastRecentlyUsed::new(n)),
+                Box::new(AgingLeastRecentlyUsed::new(n, PAGING_AGING_INTERVAL)),
+            )
+        );
+    }
+    let sizes = feeder.working_set_sizes(PAGING_WORKING_SET_WINDOW as usize);
+    let timeline = sizes
+        .chunks(PAGING_WORKING_SET_INTERVAL)
+        .map(|x| format!("{:.1}", x.iter().sum::<usize>() as f64 / x.len() as f64))
+        .collect::<Vec<String>>();
+    execute_page_fault_frequency(feeder);
+    check_belady_anomalies(feeder);
+    println!(
+        "Working set size (window {PAGING_WORKING_SET_WINDOW}): max {}, average per {PAGING_WORKING_SET_INTERVAL} references: {}",
+        sizes.iter().max().unwrap_or(&0),
+        timeline.join(" ")
+    );
     println!("=========================================");
 }
@@ -1,22 +1,61 @@
 pub mod paging_data_generator {
     use rand::prelude::*;
+    use rand::rngs::StdRng;
     use rand_distr::{Distribution, Normal};
+    use serde::{Deserialize, Serialize};
 
-    use crate::cpu_pager::paging::PagingAlgorithm;
+    use std::collections::HashMap;
+    use std::ops::RangeInclusive;
+
+    use crate::cpu_pager::paging::{OptimalReplacement, PagingAlgorithm};
+    use crate::paging_trace::trace::PagingTrace;
+    use crate::DEBUG;
 
     pub fn generate_page_numbers(n: usize, avg: f64, std_dev: f64) -> Vec<u32> {
-        let mut rng = thread_rng();
+        generate_page_numbers_from(&mut thread_rng(), n, avg, std_dev)
+    }
+
+    /// Same as `generate_page_numbers`, drawing from the given random number generator
+    ///
+    /// # Arguments
+    /// * `rng` - &mut impl Rng - Source of randomness, seed it for a reproducible sequence
+    /// * `n` - usize - Number of page references
+    /// * `avg` - f64 - Mean page number
+    /// * `std_dev` - f64 - Standard deviation of the page numbers
+    pub fn generate_page_numbers_from(
+        rng: &mut impl Rng,
+        n: usize,
+        avg: f64,
+        std_dev: f64,
+    ) -> Vec<u32> {
         let normal = Normal::new(avg, std_dev).expect("Invalid parameters");
         let mut data = Vec::new();
         for _ in 0..n {
-            data.push(normal.sample(&mut rng));
+            data.push(normal.sample(rng));
         }
         data.into_iter().map(|x| x as u32).collect()
     }
 
+    /// Page reference as stored in the JSON files, either a bare page number (read)
+    /// or an object with the write flag
+    #[derive(Serialize, Deserialize)]
+    #[serde(untagged)]
+    enum Reference {
+        Read(u32),
+        Access {
+            page: u32,
+            #[serde(default)]
+            write: bool,
+        },
+    }
+
     pub struct Feeder {
         pub pages: Vec<u32>,
+        /// Whether the page is modified, for every reference in `pages`
+        pub writes: Vec<bool>,
         pub functions: Vec<Box<dyn PagingAlgorithm>>,
+        /// Frame-by-frame trace of every fed function, named by the algorithm and its frames
+        pub traces: Vec<(String, PagingTrace)>,
     }
 
     fn generic_test_data() -> Vec<u32> {
@@ -25,18 +64,28 @@ pub mod paging_data_generator {
 
     impl Default for Feeder {
         fn default() -> Self {
-            Feeder {
-                pages: generic_test_data(),
-                functions: Vec::new(),
-            }
+            Feeder::new(generic_test_data())
         }
     }
 
     impl Feeder {
         pub fn new(pages: Vec<u32>) -> Feeder {
             Feeder {
+                writes: vec![false; pages.len()],
                 pages,
                 functions: Vec::new(),
+                traces: Vec::new(),
+            }
+        }
+
+        /// Create a Feeder from (page, write) references
+        pub fn from_references(references: Vec<(u32, bool)>) -> Feeder {
+            let (pages, writes) = references.into_iter().unzip();
+            Feeder {
+                pages,
+                writes,
+                functions: Vec::new(),
+                traces: Vec::new(),
             }
         }
 
@@ -68,19 +117,34 @@ pub mod paging_data_generator {
         /// # Returns
         /// * A Feeder object with the processes loaded from the JSON string
         pub fn from_deserialized_pages(json: String) -> Feeder {
-            let pages: Vec<u32> = serde_json::from_str(&json).unwrap();
-            Feeder {
-                pages,
-                functions: Vec::new(),
-            }
+            let references: Vec<Reference> = serde_json::from_str(&json).unwrap();
+            Feeder::from_references(
+                references
+                    .into_iter()
+                    .map(|x| match x {
+                        Reference::Read(page) => (page, false),
+                        Reference::Access { page, write } => (page, write),
+                    })
+                    .collect(),
+            )
         }
 
-        /// Serialize the processes into a JSON-standard String
+        /// Serialize the processes into a JSON-standard String,
+        /// pages are written as bare numbers unless some of them are modified
         ///
         /// # Returns
         /// * A string containing the JSON
         pub fn to_serialized_pages(&self) -> String {
-            serde_json::to_string(&self.pages).unwrap()
+            if !self.writes.contains(&true) {
+                return serde_json::to_string(&self.pages).unwrap();
+            }
+            let references = self
+                .pages
+                .iter()
+                .zip(self.writes.iter())
+                .map(|(&page, &write)| Reference::Access { page, write })
+                .collect::<Vec<Reference>>();
+            serde_json::to_string(&references).unwrap()
         }
 
         /// Export the processes into a JSON file
@@ -108,16 +172,212 @@ pub mod paging_data_generator {
         }
 
         pub fn feed(&mut self) {
+            let mut optimal: HashMap<usize, u32> = HashMap::new();
             for function in self.functions.iter_mut() {
                 let mut total_page_faults = 0;
-                for page in self.pages.iter() {
-                    if function.page_in(*page) {
+                let mut trace = PagingTrace::default();
+                for (page, write) in self.pages.iter().zip(self.writes.iter()) {
+                    let outcome = function.reference(*page, *write);
+                    if outcome.is_fault() {
                         total_page_faults += 1;
                     }
-                    // println!("Page: {}, State: {:?}", page, function);
+                    trace.record(*page, outcome, &function.resident());
+                    if DEBUG {
+                        println!(
+                            "Page: {}, {:?}, Frames: {:?}",
+                            page,
+                            outcome,
+                            function.resident()
+                        );
+                    }
+                }
+                // Baseline is computed once for every number of frames
+                let page_size = function.page_size();
+                let optimal_faults = *optimal.entry(page_size).or_insert_with(|| {
+                    let mut optimal = OptimalReplacement::new(page_size, self.pages.clone());
+                    self.pages.iter().filter(|x| optimal.page_in(**x)).count() as u32
+                });
+                let write_backs = match function.write_backs() {
+                    Some(write_backs) => write_backs.to_string(),
+                    None => "--".to_string(),
+                };
+                self.traces
+                    .push((format!("{}_{}", function.name(), page_size), trace));
+                println!(
+                    "{}({}): Total page faults: {}, {:.2}x OPT, write-backs: {}",
+                    function.name(),
+                    page_size,
+                    total_page_faults,
+                    total_page_faults as f64 / optimal_faults.max(1) as f64,
+                    write_backs
+                );
+            }
+        }
+
+        /// Positions of the references on which the algorithm yields a Page Fault
+        ///
+        /// # Arguments
+        /// * `function` - &mut dyn PagingAlgorithm - Algorithm to be run
+        ///
+        /// # Returns
+        /// * Vec<usize> - Indices into the loaded pages, in ascending order
+        pub fn fault_positions(&self, function: &mut dyn PagingAlgorithm) -> Vec<usize> {
+            self.pages
+                .iter()
+                .zip(self.writes.iter())
+                .enumerate()
+                .filter(|(_, (page, write))| function.access(**page, **write))
+                .map(|(position, _)| position)
+                .collect()
+        }
+
+        /// Sweep the number of frames looking for Belady's anomaly
+        ///
+        /// # Arguments
+        /// * `factory` - &dyn Fn(usize) -> Box<dyn PagingAlgorithm> - Creates the algorithm with the given number of frames
+        /// * `frames` - RangeInclusive<usize> - Numbers of frames to be compared
+        ///
+        /// # Returns
+        /// * Vec<(usize, u32, u32)> - Every n where faults(n + 1) > faults(n), with both fault counts
+        pub fn belady_anomalies(
+            &self,
+            factory: &dyn Fn(usize) -> Box<dyn PagingAlgorithm>,
+            frames: RangeInclusive<usize>,
+        ) -> Vec<(usize, u32, u32)> {
+            let faults = frames
+                .map(|n| (n, self.fault_positions(factory(n).as_mut()).len() as u32))
+                .collect::<Vec<(usize, u32)>>();
+            faults
+                .windows(2)
+                .filter(|x| x[1].1 > x[0].1)
+                .map(|x| (x[0].0, x[0].1, x[1].1))
+                .collect()
+        }
+
+        /// Size of the working set after every reference, the number of distinct pages
+        /// among the last `window` references
+        ///
+        /// # Arguments
+        /// * `window` - usize - Number of references the working set spans
+        ///
+        /// # Returns
+        /// * Vec<usize> - Working set size at every position of the loaded pages
+        pub fn working_set_sizes(&self, window: usize) -> Vec<usize> {
+            let mut counts: HashMap<u32, usize> = HashMap::new();
+            let mut sizes = Vec::with_capacity(self.pages.len());
+            for (position, page) in self.pages.iter().enumerate() {
+                *counts.entry(*page).or_insert(0) += 1;
+                if position >= window {
+                    let old = self.pages[position - window];
+                    let count = counts.get_mut(&old).unwrap();
+                    *count -= 1;
+                    if *count == 0 {
+                        counts.remove(&old);
+                    }
+                }
+                sizes.push(counts.len());
+            }
+            sizes
+        }
+
+        /// Compare an approximation against the exact algorithm on the loaded pages
+        ///
+        /// # Arguments
+        /// * `exact` - Box<dyn PagingAlgorithm> - Algorithm used as the reference
+        /// * `approximation` - Box<dyn PagingAlgorithm> - Algorithm approximating the reference
+        ///
+        /// # Returns
+        /// * String - Page faults of both, relative error of the approximation's fault count
+        ///   and share of references on which only one of them yielded a Page Fault
+        pub fn approximation_error(
+            &self,
+            mut exact: Box<dyn PagingAlgorithm>,
+            mut approximation: Box<dyn PagingAlgorithm>,
+        ) -> String {
+            let mut exact_faults = 0;
+            let mut approximate_faults = 0;
+            let mut mismatches = 0;
+            for page in self.pages.iter() {
+                let exact_fault = exact.page_in(*page);
+                let approximate_fault = approximation.page_in(*page);
+                exact_faults += exact_fault as u32;
+                approximate_faults += approximate_fault as u32;
+                mismatches += (exact_fault != approximate_fault) as u32;
+            }
+            format!(
+                "{} vs {}: faults {} vs {}, fault error {:.2}%, mismatched references {:.2}%",
+                approximation.name(),
+                exact.name(),
+                approximate_faults,
+                exact_faults,
+                (approximate_faults as f64 - exact_faults as f64) / exact_faults.max(1) as f64
+                    * 100.0,
+                mismatches as f64 / self.pages.len().max(1) as f64 * 100.0
+            )
+        }
+    }
+
+    /// Search random reference strings for Belady's anomaly between `frames` and `frames + 1`
+    ///
+    /// Strings are tried from the shortest, the first anomalous one is shrunk by removing
+    /// references as long as the anomaly persists, so the result is a minimal counterexample
+    /// in the sense that no single reference can be dropped from it. Without frames, or with
+    /// pages fitting into `frames + 1` which fault only on their first reference, there's
+    /// nothing to search
+    ///
+    /// # Arguments
+    /// * `factory` - &dyn Fn(usize) -> Box<dyn PagingAlgorithm> - Creates the algorithm with the given number of frames
+    /// * `frames` - usize - Smaller of the two compared numbers of frames
+    /// * `pages` - u32 - Number of distinct pages the strings are made of
+    /// * `max_length` - usize - Length of the longest string tried
+    /// * `attempts` - usize - Strings tried for every length
+    /// * `seed` - u64 - Seed of the random generator, the same seed yields the same search
+    ///
+    /// # Returns
+    /// * Option<Vec<u32>> - Counterexample, None if none was found
+    pub fn find_belady_counterexample(
+        factory: &dyn Fn(usize) -> Box<dyn PagingAlgorithm>,
+        frames: usize,
+        pages: u32,
+        max_length: usize,
+        attempts: usize,
+        seed: u64,
+    ) -> Option<Vec<u32>> {
+        if frames == 0 || pages as usize <= frames + 1 || max_length == 0 {
+            return None;
+        }
+        let anomalous = |references: &Vec<u32>| {
+            !Feeder::new(references.clone())
+                .belady_anomalies(factory, frames..=frames + 1)
+                .is_empty()
+        };
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut found = None;
+        'search: for length in 1..=max_length {
+            for _ in 0..attempts {
+                let references = (0..length)
+                    .map(|_| rng.gen_range(0..pages))
+                    .collect::<Vec<u32>>();
+                if anomalous(&references) {
+                    found = Some(references);
+                    break 'search;
+                }
+            }
+        }
+        let mut references = found?;
+        let mut shrunk = true;
+        while shrunk {
+            shrunk = false;
+            for index in 0..references.len() {
+                let mut candidate = references.clone();
+                candidate.remove(index);
+                if anomalous(&candidate) {
+                    references = candidate;
+                    shrunk = true;
+                    break;
                 }
-                println!("Total page faults: {}", total_page_faults);
             }
         }
+        Some(references)
     }
 }
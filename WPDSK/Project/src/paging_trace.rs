@@ -0,0 +1,114 @@
+pub mod trace {
+    use crate::cpu_pager::paging::PageOutcome;
+
+    /// Contents of every frame after every reference of a single paging run
+    ///
+    /// Every frame of the algorithm is a row, pages stay in the row of the frame they
+    /// were loaded into, so the table reads like the ones drawn by hand
+    #[derive(Debug, Default)]
+    pub struct PagingTrace {
+        pub pages: Vec<u32>,
+        /// Frame contents after every reference, indexed by row
+        pub columns: Vec<Vec<Option<u32>>>,
+        pub faults: Vec<bool>,
+        rows: Vec<Option<u32>>,
+    }
+
+    impl PagingTrace {
+        /// Record the reference with the resident set of the algorithm after it
+        ///
+        /// # Arguments
+        /// * `page` - u32 - Referenced page
+        /// * `outcome` - PageOutcome - Outcome of the reference
+        /// * `resident` - &[u32] - Pages resident after the reference
+        pub fn record(&mut self, page: u32, outcome: PageOutcome, resident: &[u32]) {
+            for row in self.rows.iter_mut() {
+                if row.is_some_and(|x| !resident.contains(&x)) {
+                    *row = None;
+                }
+            }
+            let frame = outcome.frame();
+            if frame >= self.rows.len() {
+                self.rows.resize(frame + 1, None);
+            }
+            self.rows[frame] = Some(page);
+            self.pages.push(page);
+            self.columns.push(self.rows.clone());
+            self.faults.push(outcome.is_fault());
+        }
+
+        /// Table rows: the references, one row per frame and the Page Fault marks
+        fn table(&self) -> Vec<Vec<String>> {
+            let frames = self.columns.iter().map(|x| x.len()).max().unwrap_or(0);
+            let mut table = vec![std::iter::once("Reference".to_string())
+                .chain(self.pages.iter().map(|x| x.to_string()))
+                .collect::<Vec<String>>()];
+            for frame in 0..frames {
+                table.push(
+                    std::iter::once(format!("Frame {}", frame))
+                        .chain(self.columns.iter().map(|column| {
+                            match column.get(frame).copied().flatten() {
+                                Some(page) => page.to_string(),
+                                None => String::new(),
+                            }
+                        }))
+                        .collect(),
+                );
+            }
+            table.push(
+                std::iter::once("Fault".to_string())
+                    .chain(self.faults.iter().map(|&x| match x {
+                        true => "F".to_string(),
+                        false => String::new(),
+                    }))
+                    .collect(),
+            );
+            table
+        }
+    }
+
+    /// Render the trace as CSV, one column per reference
+    pub fn to_csv(trace: &PagingTrace) -> String {
+        let mut result = String::new();
+        for row in trace.table() {
+            result.push_str(&row.join(";"));
+            result.push('\n');
+        }
+        result
+    }
+
+    /// Render the trace as a Markdown table, which also reads as an ASCII grid
+    ///
+    /// # Example
+    /// ```text
+    /// | Reference | 1 | 2 | 1 | 3 |
+    /// |-----------|---|---|---|---|
+    /// | Frame 0   | 1 | 1 | 1 | 3 |
+    /// | Frame 1   |   | 2 | 2 | 2 |
+    /// | Fault     | F | F |   | F |
+    /// ```
+    pub fn to_markdown(trace: &PagingTrace) -> String {
+        let table = trace.table();
+        let widths = (0..table[0].len())
+            .map(|column| table.iter().map(|row| row[column].len()).max().unwrap())
+            .collect::<Vec<usize>>();
+        let line = |row: &Vec<String>| {
+            let cells = row
+                .iter()
+                .zip(widths.iter())
+                .map(|(cell, width)| format!(" {:<width$} ", cell, width = width))
+                .collect::<Vec<String>>();
+            format!("|{}|\n", cells.join("|"))
+        };
+        let mut result = line(&table[0]);
+        let separator = widths
+            .iter()
+            .map(|width| "-".repeat(width + 2))
+            .collect::<Vec<String>>();
+        result.push_str(&format!("|{}|\n", separator.join("|")));
+        for row in table.iter().skip(1) {
+            result.push_str(&line(row));
+        }
+        result
+    }
+}
@@ -1,11 +1,16 @@
 pub mod scheduler_data_generator {
     use rand_distr::Uniform;
+    use serde::{Deserialize, Serialize};
+    use std::cmp::Reverse;
+    use std::collections::{BinaryHeap, HashMap};
 
     use rand::thread_rng;
     use rand_distr::{Distribution, Normal};
 
-    use crate::cpu_scheduler;
-    use crate::cpu_scheduler::scheduler::{Cpu, Process};
+    use crate::cpu_multicore::multicore::MultiCore;
+    use crate::cpu_scheduler::scheduler::{hyperperiod, release_jobs, Cpu, PeriodicTask, Process};
+    use crate::event_log::events::{check_consistency, EventLog, LogEntry};
+    use crate::gantt_chart::gantt::{Slot, Timeline};
     use crate::DEBUG;
 
     pub fn generate_duration_times(n: usize, avg: f64, std_dev: f64) -> Vec<u32> {
@@ -41,26 +46,36 @@ pub mod scheduler_data_generator {
                 pid: 1,
                 arrival: 0,
                 burst: 4,
+                priority: 3,
+                ..Default::default()
             },
             Process {
                 pid: 2,
                 arrival: 1,
                 burst: 3,
+                priority: 1,
+                ..Default::default()
             },
             Process {
                 pid: 3,
                 arrival: 2,
                 burst: 1,
+                priority: 4,
+                ..Default::default()
             },
             Process {
                 pid: 4,
                 arrival: 3,
                 burst: 2,
+                priority: 5,
+                ..Default::default()
             },
             Process {
                 pid: 5,
                 arrival: 4,
                 burst: 5,
+                priority: 2,
+                ..Default::default()
             },
         ]
     }
@@ -70,49 +85,324 @@ pub mod scheduler_data_generator {
         pid: u32,
         arrival: u32,
         burst: u32,
+        priority: u32,
+        deadline: Option<u32>,
+        /// Total time of I/O bursts
+        io: u32,
+        /// Total time spent in the Blocked state, waiting for and using the I/O device
+        blocked: u32,
+        /// Time from arrival to the first time on CPU
+        response: u32,
         turnaround: u32,
         waiting: u32,
     }
 
+    /// Summary metrics of a single algorithm run
+    #[derive(Debug, Default, Serialize)]
+    pub struct Metrics {
+        pub algorithm: String,
+        pub processes: usize,
+        /// Completion time of the last process
+        pub makespan: u32,
+        pub average_turnaround: f64,
+        pub average_waiting: f64,
+        /// Average time from arrival to the first time on CPU
+        pub average_response: f64,
+        pub max_waiting: u32,
+        pub p50_waiting: u32,
+        pub p95_waiting: u32,
+        pub p99_waiting: u32,
+        /// Fraction of the makespan (per core) the CPU was running processes
+        pub cpu_utilization: f64,
+        pub idle_ticks: u32,
+        pub context_switches: u32,
+        /// Fraction of the makespan spent on context switching
+        pub switch_overhead: f64,
+        /// Jain's fairness index of burst / turnaround, 1 means every process was slowed down equally
+        pub jain_fairness: f64,
+        pub throughput_window: u32,
+        /// Number of processes completed in every consecutive window
+        pub throughput: Vec<u32>,
+    }
+
+    /// Nearest-rank percentile of sorted values
+    fn percentile(sorted: &[u32], p: f64) -> u32 {
+        if sorted.is_empty() {
+            return 0;
+        }
+        let rank = (p / 100.0 * sorted.len() as f64).ceil() as usize;
+        sorted[rank.clamp(1, sorted.len()) - 1]
+    }
+
+    impl Metrics {
+        /// # Arguments
+        /// * `algorithm` - Name of the algorithm
+        /// * `output` - Entries of all the finished processes
+        /// * `cores` - Number of cores the processes were run on
+        /// * `context_switches` - Number of times the running process has changed
+        /// * `switch_ticks` - Ticks spent on context switching
+        /// * `throughput_window` - Length of the windows in which throughput is measured
+        fn new(
+            algorithm: String,
+            output: &[OutputProcessEntry],
+            cores: u32,
+            context_switches: u32,
+            switch_ticks: u32,
+            throughput_window: u32,
+        ) -> Metrics {
+            let n = output.len().max(1) as f64;
+            let makespan = output
+                .iter()
+                .map(|x| x.arrival + x.turnaround)
+                .max()
+                .unwrap_or(0);
+            let busy = output.iter().map(|x| x.burst).sum::<u32>();
+            let capacity = makespan * cores;
+            let mut waiting = output.iter().map(|x| x.waiting).collect::<Vec<u32>>();
+            waiting.sort();
+            let shares = output
+                .iter()
+                .map(|x| x.burst as f64 / x.turnaround.max(x.burst).max(1) as f64)
+                .collect::<Vec<f64>>();
+            let jain_fairness = shares.iter().sum::<f64>().powi(2)
+                / (n * shares.iter().map(|x| x * x).sum::<f64>()).max(f64::MIN_POSITIVE);
+            let window = throughput_window.max(1);
+            let mut throughput = vec![0; (makespan / window + 1) as usize];
+            for entry in output.iter() {
+                throughput[((entry.arrival + entry.turnaround) / window) as usize] += 1;
+            }
+            Metrics {
+                algorithm,
+                processes: output.len(),
+                makespan,
+                average_turnaround: output.iter().map(|x| x.turnaround).sum::<u32>() as f64 / n,
+                average_waiting: waiting.iter().sum::<u32>() as f64 / n,
+                average_response: output.iter().map(|x| x.response).sum::<u32>() as f64 / n,
+                max_waiting: waiting.last().copied().unwrap_or(0),
+                p50_waiting: percentile(&waiting, 50.0),
+                p95_waiting: percentile(&waiting, 95.0),
+                p99_waiting: percentile(&waiting, 99.0),
+                cpu_utilization: busy as f64 / capacity.max(1) as f64,
+                idle_ticks: capacity.saturating_sub(busy + switch_ticks),
+                context_switches,
+                switch_overhead: switch_ticks as f64 / capacity.max(1) as f64,
+                jain_fairness,
+                throughput_window: window,
+                throughput,
+            }
+        }
+    }
+
+    /// Process as stored in the JSON files, with optional sequence of alternating
+    /// CPU and I/O bursts (CPU, IO, CPU, ...), first of which replaces the `burst` field
+    #[derive(Serialize, Deserialize)]
+    struct ProcessRecord {
+        #[serde(flatten)]
+        process: Process,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        bursts: Option<Vec<u32>>,
+    }
+
     pub fn parse_test_data(processes: &[Process]) -> String {
         let mut result = String::new();
-        result.push_str("PID;Arrival;Burst\n");
+        result.push_str("PID;Arrival;Burst;Priority;Nice;Tickets\n");
         for process in processes.iter() {
             result.push_str(&format!(
-                "{};{};{}\n",
-                process.pid, process.arrival, process.burst
+                "{};{};{};{};{};{}\n",
+                process.pid,
+                process.arrival,
+                process.burst,
+                process.priority,
+                process.nice,
+                process.tickets
             ));
         }
         result
     }
 
+    /// Run the utilization based schedulability tests for the task set
+    ///
+    /// Rate Monotonic is checked against the Liu & Layland bound n(2^(1/n) - 1),
+    /// Earliest Deadline First against the density bound of 1 (exact for implicit deadlines)
+    pub fn schedulability_test(tasks: &[PeriodicTask]) -> String {
+        let mut result = String::new();
+        result.push_str("Task;Period;WCET;Deadline;Utilization\n");
+        for task in tasks.iter() {
+            result.push_str(&format!(
+                "{};{};{};{};{:.4}\n",
+                task.id,
+                task.period,
+                task.wcet,
+                task.deadline,
+                task.wcet as f64 / task.period as f64
+            ));
+        }
+        let n = tasks.len() as f64;
+        let utilization = tasks
+            .iter()
+            .map(|x| x.wcet as f64 / x.period as f64)
+            .sum::<f64>();
+        let density = tasks
+            .iter()
+            .map(|x| x.wcet as f64 / x.period.min(x.deadline) as f64)
+            .sum::<f64>();
+        let rm_bound = n * (2f64.powf(1.0 / n) - 1.0);
+        let rm_verdict = if density <= rm_bound {
+            "Schedulable"
+        } else if utilization <= 1.0 {
+            "Inconclusive"
+        } else {
+            "Not schedulable"
+        };
+        let edf_verdict = if density <= 1.0 {
+            "Schedulable"
+        } else if utilization <= 1.0 {
+            "Inconclusive"
+        } else {
+            "Not schedulable"
+        };
+        result.push_str(&format!("Utilization;--;--;--;{:.4}\n", utilization));
+        result.push_str(&format!("Density;--;--;--;{:.4}\n", density));
+        result.push_str(&format!(
+            "RateMonotonic bound;--;--;{};{:.4}\n",
+            rm_verdict, rm_bound
+        ));
+        result.push_str(&format!(
+            "EarliestDeadlineFirst bound;--;--;{};{:.4}\n",
+            edf_verdict, 1.0
+        ));
+        result
+    }
+
+    /// Total CPU and I/O time of the process over all of its bursts
+    fn burst_totals(bursts: &HashMap<u32, Vec<u32>>, process: &Process) -> (u32, u32) {
+        match bursts.get(&process.pid) {
+            Some(sequence) => (
+                sequence.iter().step_by(2).sum(),
+                sequence.iter().skip(1).step_by(2).sum(),
+            ),
+            None => (process.burst, 0),
+        }
+    }
+
+    /// Move the process to its next cycle of the burst sequence, if it has one
+    ///
+    /// # Returns
+    /// * Option<(u32, u32)> - I/O burst and the CPU burst following it, trailing I/O burst
+    ///   without CPU burst after it is ignored
+    fn next_cycle(
+        bursts: &HashMap<u32, Vec<u32>>,
+        cycle: &mut HashMap<u32, usize>,
+        pid: u32,
+    ) -> Option<(u32, u32)> {
+        let sequence = bursts.get(&pid)?;
+        let index = cycle.get(&pid).copied().unwrap_or(0);
+        if index + 2 >= sequence.len() {
+            return None;
+        }
+        cycle.insert(pid, index + 2);
+        Some((sequence[index + 1], sequence[index + 2]))
+    }
+
+    /// Pending arrivals of the event-driven engine, both new processes and processes
+    /// returning from I/O, ordered by arrival time
+    #[derive(Default)]
+    struct EventQueue {
+        /// (Arrival, index in `processes`) of every pending arrival
+        events: BinaryHeap<Reverse<(u32, usize)>>,
+        processes: Vec<Process>,
+    }
+
+    impl EventQueue {
+        fn push(&mut self, process: Process) {
+            self.events
+                .push(Reverse((process.arrival, self.processes.len())));
+            self.processes.push(process);
+        }
+
+        fn is_empty(&self) -> bool {
+            self.events.is_empty()
+        }
+
+        /// Time of the earliest pending arrival
+        fn next_arrival(&self) -> Option<u32> {
+            self.events.peek().map(|Reverse((arrival, _))| *arrival)
+        }
+
+        /// Remove all processes that have arrived by `timer`
+        ///
+        /// # Returns
+        /// * Vec<Process> - Arrived processes in the order they were queued
+        fn pop_arrived(&mut self, timer: u32) -> Vec<Process> {
+            let mut arrived = Vec::new();
+            while let Some(&Reverse((arrival, index))) = self.events.peek() {
+                if arrival > timer {
+                    break;
+                }
+                self.events.pop();
+                arrived.push(index);
+            }
+            // More than one arrival time is delivered at once after a context switch
+            arrived.sort();
+            arrived.into_iter().map(|x| self.processes[x]).collect()
+        }
+    }
+
     // type TraitSpecificFunction = fn(&mut Cpu, Option<Process>, u32) -> (u32, Option<u32>);
 
     pub struct Feeder {
         pub processes: Vec<Process>,
+        /// Periodic tasks the processes were released from, empty for non real-time data
+        pub tasks: Vec<PeriodicTask>,
+        /// Burst sequences (CPU, IO, CPU, ...) of processes alternating with I/O, by PID
+        pub bursts: HashMap<u32, Vec<u32>>,
+        /// Ticks during which no process progresses, whenever the running process changes
+        pub context_switch_cost: u32,
+        /// (Algorithm name, state of the CPU in every tick from time 0) of every single core run, filled by `feed`
+        pub timelines: Vec<(String, Timeline)>,
+        /// (Algorithm name, events of every process) of every single core run, filled by `feed`
+        pub event_logs: Vec<(String, Vec<LogEntry>)>,
+        /// Length of the windows in which throughput is measured
+        pub throughput_window: u32,
+        /// Metrics of every run, single and multi-core, filled by `feed`
+        pub summaries: Vec<Metrics>,
+        /// Jump over the loops in which nothing but the running process progresses,
+        /// instead of simulating every tick, results are the same either way
+        pub event_driven: bool,
         functions: Vec<Box<dyn Cpu>>,
+        multicores: Vec<MultiCore>,
     }
 
     impl Default for Feeder {
         fn default() -> Self {
             let processes = generic_test_data();
-            Feeder {
-                processes,
-                functions: Vec::new(),
-            }
+            Feeder::with_processes(processes)
         }
     }
 
     impl From<Vec<Process>> for Feeder {
         fn from(processes: Vec<Process>) -> Self {
+            Feeder::with_processes(processes)
+        }
+    }
+
+    impl Feeder {
+        fn with_processes(processes: Vec<Process>) -> Feeder {
             Feeder {
                 processes,
+                tasks: Vec::new(),
+                bursts: HashMap::new(),
+                context_switch_cost: 0,
+                throughput_window: 50,
+                timelines: Vec::new(),
+                event_logs: Vec::new(),
+                summaries: Vec::new(),
+                event_driven: true,
                 functions: Vec::new(),
+                multicores: Vec::new(),
             }
         }
-    }
 
-    impl Feeder {
         pub fn new(
             n: usize,
             arrival_range_start: u32,
@@ -126,7 +416,7 @@ pub mod scheduler_data_generator {
                 .into_iter()
                 .zip(duration_times)
                 .collect::<Vec<(u32, u32)>>();
-            processes.sort_by(|a, b| a.0.cmp(&b.0));
+            processes.sort_by_key(|a| a.0);
             let processes: Vec<Process> = processes
                 .into_iter()
                 .enumerate()
@@ -134,11 +424,67 @@ pub mod scheduler_data_generator {
                     pid: i as u32,
                     arrival: arrival_time,
                     burst: duration_time,
+                    ..Default::default()
                 })
                 .collect();
+            Feeder::with_processes(processes)
+        }
+
+        /// Release jobs of the periodic tasks in range 0..horizon and load them into new Feeder object
+        ///
+        /// # Arguments
+        /// * `tasks` - A vector of periodic tasks
+        /// * `horizon` - Time after which no more jobs are released
+        ///
+        /// # Returns
+        /// * A Feeder object with the released jobs as processes
+        pub fn from_periodic_tasks(tasks: Vec<PeriodicTask>, horizon: u32) -> Feeder {
+            let processes = release_jobs(&tasks, horizon);
             Feeder {
-                processes,
-                functions: Vec::new(),
+                tasks,
+                ..Feeder::with_processes(processes)
+            }
+        }
+
+        /// Import the JSON file and deserialize it into array of PeriodicTasks
+        /// Then release their jobs over one hyperperiod into new Feeder object
+        ///
+        /// # Arguments
+        /// * `filename` - A string containing the JSON filename or path
+        /// * `horizon_limit` - Upper limit of the simulated time, for task sets with long hyperperiods
+        ///
+        /// # Returns
+        /// * A Feeder object with the jobs released by the tasks loaded from the JSON file
+        pub fn import_tasks_from_file(filename: String, horizon_limit: u32) -> Feeder {
+            let json_string = std::fs::read_to_string(filename);
+            let json_string = match json_string {
+                Ok(json_string) => json_string,
+                Err(e) => {
+                    panic!("Error reading file: {}", e);
+                }
+            };
+            let tasks: Vec<PeriodicTask> = serde_json::from_str(&json_string).unwrap();
+            let horizon = hyperperiod(&tasks).min(horizon_limit);
+            Feeder::from_periodic_tasks(tasks, horizon)
+        }
+
+        /// Export the periodic tasks into a JSON file
+        ///
+        /// # Arguments
+        /// * `filename` - A string containing the JSON filename or path
+        ///
+        /// # Returns
+        /// * None - Everything is written to file successfully and function exits, otherwise it panics
+        pub fn export_tasks_to_file(&self, filename: String) {
+            let json_string = serde_json::to_string(&self.tasks).unwrap();
+            let result = std::fs::write(filename, json_string);
+            match result {
+                Ok(_) => {
+                    println!("File saved successfully");
+                }
+                Err(e) => {
+                    panic!("Error writing file: {}", e);
+                }
             }
         }
 
@@ -170,10 +516,42 @@ pub mod scheduler_data_generator {
         /// # Returns
         /// * A Feeder object with the processes loaded from the JSON string
         pub fn from_deserialized_processes(json: String) -> Feeder {
-            let processes: Vec<Process> = serde_json::from_str(&json).unwrap();
+            let records: Vec<ProcessRecord> = serde_json::from_str(&json).unwrap();
+            Feeder::from_burst_sequences(
+                records
+                    .into_iter()
+                    .map(|x| (x.process, x.bursts.unwrap_or_default()))
+                    .collect(),
+            )
+        }
+
+        /// Load processes alternating between CPU and I/O bursts into new Feeder object
+        ///
+        /// # Arguments
+        /// * `processes` - A vector of processes with their burst sequences (CPU, IO, CPU, ...),
+        ///   empty sequence means a single CPU burst from the `burst` field
+        ///
+        /// # Returns
+        /// * A Feeder object with the processes and their burst sequences
+        pub fn from_burst_sequences(processes: Vec<(Process, Vec<u32>)>) -> Feeder {
+            let mut bursts = HashMap::new();
+            let processes = processes
+                .into_iter()
+                .map(|(process, sequence)| {
+                    if sequence.is_empty() {
+                        return process;
+                    }
+                    let process = Process {
+                        burst: sequence[0],
+                        ..process
+                    };
+                    bursts.insert(process.pid, sequence);
+                    process
+                })
+                .collect();
             Feeder {
-                processes,
-                functions: Vec::new(),
+                bursts,
+                ..Feeder::with_processes(processes)
             }
         }
 
@@ -182,7 +560,15 @@ pub mod scheduler_data_generator {
         /// # Returns
         /// * A string containing the JSON
         pub fn to_serialized_processes(&self) -> String {
-            serde_json::to_string(&self.processes).unwrap()
+            let records = self
+                .processes
+                .iter()
+                .map(|x| ProcessRecord {
+                    process: *x,
+                    bursts: self.bursts.get(&x.pid).cloned(),
+                })
+                .collect::<Vec<ProcessRecord>>();
+            serde_json::to_string(&records).unwrap()
         }
 
         /// Export the processes into a JSON file
@@ -209,80 +595,361 @@ pub mod scheduler_data_generator {
             self.functions.push(f);
         }
 
-        fn parse_output(output: Vec<OutputProcessEntry>) -> String {
+        pub fn add_multicore(&mut self, multicore: MultiCore) {
+            self.multicores.push(multicore);
+        }
+
+        /// Table of the finished processes followed by the summary metrics of the run
+        fn parse_output(output: Vec<OutputProcessEntry>, metrics: &Metrics) -> String {
             let mut output = output;
             // Sort by PID, for algorithms other than FCFS
-            output.sort_by(|a, b| a.pid.cmp(&b.pid));
+            output.sort_by_key(|a| a.pid);
             let mut result = String::new();
-            result.push_str("PID;Arrival;Burst;Turnaround;Waiting\n");
-            let avg_turnaround =
-                output.iter().map(|x| x.turnaround).sum::<u32>() as f64 / output.len() as f64;
-            let avg_waiting =
-                output.iter().map(|x| x.waiting).sum::<u32>() as f64 / output.len() as f64;
+            result.push_str("PID;Arrival;Burst;Priority;Turnaround;Waiting;Response\n");
             for entry in output {
                 result.push_str(&format!(
-                    "{};{};{};{};{}\n",
-                    entry.pid, entry.arrival, entry.burst, entry.turnaround, entry.waiting
+                    "{};{};{};{};{};{};{}\n",
+                    entry.pid,
+                    entry.arrival,
+                    entry.burst,
+                    entry.priority,
+                    entry.turnaround,
+                    entry.waiting,
+                    entry.response
                 ));
             }
             result.push_str(&format!(
-                "Average;--;--;{};{}\n",
-                avg_turnaround, avg_waiting
+                "Average;--;--;--;{};{};{}\n",
+                metrics.average_turnaround, metrics.average_waiting, metrics.average_response
+            ));
+            result.push_str(&format!("Makespan;{}\n", metrics.makespan));
+            result.push_str(&format!(
+                "Waiting max/p50/p95/p99;{};{};{};{}\n",
+                metrics.max_waiting, metrics.p50_waiting, metrics.p95_waiting, metrics.p99_waiting
+            ));
+            result.push_str(&format!("CPU utilization;{:.4}\n", metrics.cpu_utilization));
+            result.push_str(&format!("Idle ticks;{}\n", metrics.idle_ticks));
+            result.push_str(&format!("Context switches;{}\n", metrics.context_switches));
+            result.push_str(&format!("Switch overhead;{:.4}\n", metrics.switch_overhead));
+            result.push_str(&format!(
+                "Jain's fairness index;{:.4}\n",
+                metrics.jain_fairness
+            ));
+            result.push_str(&format!(
+                "Throughput per {} ticks;{}\n",
+                metrics.throughput_window,
+                metrics
+                    .throughput
+                    .iter()
+                    .map(|x| x.to_string())
+                    .collect::<Vec<String>>()
+                    .join(";")
             ));
             result
         }
 
+        /// Lateness of every real-time job, negative values mean the job finished before its deadline
+        fn parse_deadlines(output: &[OutputProcessEntry]) -> Option<String> {
+            let mut jobs = output
+                .iter()
+                .filter_map(|x| x.deadline.map(|deadline| (x, deadline)))
+                .collect::<Vec<(&OutputProcessEntry, u32)>>();
+            if jobs.is_empty() {
+                return None;
+            }
+            jobs.sort_by_key(|(entry, _)| entry.pid);
+            let mut result = String::new();
+            result.push_str("PID;Deadline;Completion;Lateness\n");
+            let mut misses = 0;
+            for (entry, deadline) in jobs.iter() {
+                let completion = entry.arrival + entry.turnaround;
+                let lateness = completion as i64 - *deadline as i64;
+                if lateness > 0 {
+                    misses += 1;
+                }
+                result.push_str(&format!(
+                    "{};{};{};{}\n",
+                    entry.pid, deadline, completion, lateness
+                ));
+            }
+            result.push_str(&format!("Deadline misses;--;--;{}\n", misses));
+            Some(result)
+        }
+
+        /// Fairness between I/O-bound (more time in I/O than on CPU) and CPU-bound processes,
+        /// if any of the processes did I/O
+        fn parse_io(output: &[OutputProcessEntry]) -> Option<String> {
+            if output.iter().all(|x| x.io == 0) {
+                return None;
+            }
+            let mut result = String::new();
+            result.push_str("Class;Processes;Average waiting;Average blocked;Average slowdown\n");
+            for (class, io_bound) in [("IO-bound", true), ("CPU-bound", false)] {
+                let entries = output
+                    .iter()
+                    .filter(|x| (x.io > x.burst) == io_bound)
+                    .collect::<Vec<&OutputProcessEntry>>();
+                let n = entries.len().max(1) as f64;
+                // Slowdown is turnaround relative to the time the process would take on an idle system
+                let slowdown = entries
+                    .iter()
+                    .map(|x| x.turnaround as f64 / (x.burst + x.io).max(1) as f64)
+                    .sum::<f64>();
+                result.push_str(&format!(
+                    "{};{};{:.2};{:.2};{:.2}\n",
+                    class,
+                    entries.len(),
+                    entries.iter().map(|x| x.waiting).sum::<u32>() as f64 / n,
+                    entries.iter().map(|x| x.blocked).sum::<u32>() as f64 / n,
+                    slowdown / n
+                ));
+            }
+            Some(result)
+        }
+
         pub fn feed(&mut self) -> Vec<String> {
             let mut outputs = Vec::new();
+            if !self.tasks.is_empty() {
+                outputs.push(schedulability_test(&self.tasks));
+            }
+            let processes = self
+                .processes
+                .iter()
+                .map(|x| (x.pid, *x))
+                .collect::<HashMap<u32, Process>>();
             for cpu in self.functions.iter_mut() {
                 let mut timer = 0; // Reset timer for each Algorithm
-                let mut arrivals = self.processes.clone();
-                let mut output: Vec<OutputProcessEntry> = Vec::new();
+                let mut arrivals = EventQueue::default();
+                for process in self.processes.iter() {
+                    arrivals.push(*process);
+                }
                 if DEBUG {
-                    println!("{}", cpu_scheduler::scheduler::process_table_header());
+                    println!("{}", cpu.table_header());
                 }
                 let mut current_pid;
+                // Index of the current CPU burst in the burst sequence, by PID
+                let mut cycle: HashMap<u32, usize> = HashMap::new();
+                // Single I/O device serving requests in FIFO order
+                let mut io_free_at = 0;
+                let mut last_running: Option<u32> = None;
+                let mut context_switches = 0;
+                let mut timeline = Timeline::default();
+                let mut log = EventLog::default();
                 loop {
-                    // let mut arrival = arrivals.first().cloned();
                     if arrivals.is_empty() && cpu.get_stack().is_empty() {
                         break;
                     }
-                    let arrivals_now = arrivals
-                        .iter()
-                        .filter(|x| x.arrival == timer)
-                        .map(|x| x.to_owned())
-                        .collect(); // Gather all processes that have arrived
-                    arrivals.retain(|x| x.arrival != timer); // Remove all processes that have arrived
+                    // Process which used up its burst in the last loop is only removed from
+                    // the stack in this one, but it has finished already, so its I/O burst
+                    // starts right away and a return at this tick is delivered in this loop
+                    let finished = cpu
+                        .get_stack()
+                        .first()
+                        .filter(|x| x.burst == 0)
+                        .map(|x| x.pid);
+                    if let Some(pid) = finished {
+                        // Process is Blocked until its I/O burst is done, then it's Ready again
+                        let next = next_cycle(&self.bursts, &mut cycle, pid);
+                        let completion = log.finished(pid, next.is_some());
+                        if let Some((io, burst)) = next {
+                            io_free_at = completion.max(io_free_at) + io;
+                            arrivals.push(Process {
+                                arrival: io_free_at,
+                                burst,
+                                ..processes[&pid]
+                            });
+                        }
+                    }
+                    // Jump to the next event: arrival, completion, quantum expiry or preemption
+                    let front = cpu.get_stack().first().map(|x| x.pid);
+                    if self.event_driven && !DEBUG && (front.is_none() || front == last_running) {
+                        let until_arrival = arrivals
+                            .next_arrival()
+                            .map_or(u32::MAX, |x| x.saturating_sub(timer));
+                        let loops = cpu.quiet_loops().min(until_arrival);
+                        if loops > 0 {
+                            cpu.skip_loops(loops);
+                            timer += loops;
+                            let slot = match front {
+                                Some(pid) => Slot::Run(pid),
+                                None => Slot::Idle,
+                            };
+                            if let Some(pid) = front {
+                                log.ran(pid, timeline.ticks() as u32, timer - 1);
+                            }
+                            timeline.push(slot, loops as usize);
+                            continue;
+                        }
+                    }
+                    let mut arrivals_now = arrivals.pop_arrived(timer);
+                    for process in arrivals_now.iter() {
+                        log.arrived(process.pid, process.arrival);
+                        // Empty CPU burst is finished right away, without taking the CPU
+                        if process.burst == 0 {
+                            let next = next_cycle(&self.bursts, &mut cycle, process.pid);
+                            log.finished_on_arrival(process.pid, process.arrival, next.is_some());
+                            if let Some((io, burst)) = next {
+                                io_free_at = process.arrival.max(io_free_at) + io;
+                                arrivals.push(Process {
+                                    arrival: io_free_at,
+                                    burst,
+                                    ..*process
+                                });
+                            }
+                        }
+                    }
+                    arrivals_now.retain(|x| x.burst > 0);
+                    let arrived = arrivals_now.iter().map(|x| x.pid).collect::<Vec<u32>>();
                     (timer, current_pid) = cpu.next_loop(arrivals_now, timer);
                     if DEBUG {
-                        println!(
-                            "{}",
-                            cpu_scheduler::scheduler::process_table(cpu.get_stack(), &(&timer - 1))
-                                .join("\n")
+                        println!("{}", cpu.table(&(&timer - 1)).join("\n"));
+                    }
+                    if current_pid != finished {
+                        panic!(
+                            "{} removed {:?} instead of the finished {:?}",
+                            cpu.name(),
+                            current_pid,
+                            finished
                         );
                     }
-                    if let Some(pid) = current_pid {
-                        let process = self.processes.iter().find(|&x| x.pid == pid).cloned();
-                        let turnaround = if let Some(process) = process {
-                            (timer - 2) - process.arrival
-                        } else {
-                            0
-                        };
-                        let waiting = if let Some(process) = process {
-                            turnaround - process.burst
-                        } else {
-                            0
-                        };
-                        output.push(OutputProcessEntry {
+                    // Process at the front of the stack is the one that ran in this loop,
+                    // unless it has just arrived
+                    let running = cpu
+                        .get_stack()
+                        .first()
+                        .map(|x| x.pid)
+                        .filter(|pid| !arrived.contains(pid));
+                    if running.is_some() && running != last_running {
+                        if last_running.is_some() {
+                            context_switches += 1;
+                            // Nothing progresses while switching, arrivals are delivered afterwards
+                            timer += self.context_switch_cost;
+                            // Switch happens before the new process runs
+                            timeline.push(Slot::Switch, self.context_switch_cost as usize);
+                        }
+                        last_running = running;
+                    }
+                    let slot = match running {
+                        Some(pid) => Slot::Run(pid),
+                        None => Slot::Idle,
+                    };
+                    timeline.push(slot, 1);
+                    if let Some(pid) = running {
+                        let slot = timeline.ticks() as u32 - 1;
+                        log.ran(pid, slot, slot);
+                    }
+                }
+                let expected = processes
+                    .values()
+                    .map(|x| (x.pid, burst_totals(&self.bursts, x).0))
+                    .collect::<HashMap<u32, u32>>();
+                if let Err(error) = check_consistency(&log, &expected) {
+                    panic!("{} produced inconsistent event log: {}", cpu.name(), error);
+                }
+                let output = log
+                    .process_times()
+                    .iter()
+                    .map(|times| {
+                        let process = processes[&times.pid];
+                        let (burst, io) = burst_totals(&self.bursts, &process);
+                        OutputProcessEntry {
+                            pid: times.pid,
+                            arrival: times.arrival,
+                            burst,
+                            priority: process.priority,
+                            deadline: process.deadline,
+                            io,
+                            blocked: times.blocked,
+                            response: times.response.unwrap_or(0),
+                            turnaround: times.turnaround(),
+                            waiting: times.waiting,
+                        }
+                    })
+                    .collect::<Vec<OutputProcessEntry>>();
+                let metrics = Metrics::new(
+                    cpu.name(),
+                    &output,
+                    1,
+                    context_switches,
+                    context_switches * self.context_switch_cost,
+                    self.throughput_window,
+                );
+                let deadlines = Feeder::parse_deadlines(&output);
+                let io = Feeder::parse_io(&output);
+                let mut result = Feeder::parse_output(output, &metrics);
+                if let Some(deadlines) = deadlines {
+                    result.push_str(&deadlines);
+                }
+                if let Some(io) = io {
+                    result.push_str(&io);
+                }
+                if let Some(report) = cpu.report() {
+                    result.push_str(&report);
+                }
+                outputs.push(result);
+                // Loop at timer `t` simulates the tick ending at `t`, the first one only
+                // delivers the arrivals, so slot `t` of the chart spans from `t` to `t + 1`,
+                // the last one only removes the finished process
+                timeline.pop_front();
+                if timeline.last() == Some(Slot::Idle) {
+                    timeline.pop();
+                }
+                self.timelines.push((cpu.name(), timeline));
+                self.event_logs.push((cpu.name(), log.entries));
+                self.summaries.push(metrics);
+            }
+            // Multi-core simulation runs only the first CPU burst, results of the other
+            // bursts would be compared with a different workload
+            let multicores: &[MultiCore] = match self.bursts.is_empty() {
+                true => &self.multicores,
+                false if self.multicores.is_empty() => &[],
+                false => {
+                    let note = "Multi-core simulation skipped: I/O bursts are only simulated on a single core\n";
+                    print!("{}", note);
+                    outputs.push(note.to_string());
+                    &[]
+                }
+            };
+            for multicore in multicores.iter() {
+                let run = multicore.simulate(&self.processes);
+                let output = run
+                    .completions
+                    .iter()
+                    .map(|&(pid, completion)| {
+                        let process = processes[&pid];
+                        let turnaround = completion - process.arrival;
+                        let response = run.first_run[&pid].saturating_sub(process.arrival + 1);
+                        OutputProcessEntry {
                             pid,
-                            arrival: process.unwrap().arrival,
-                            burst: process.unwrap().burst,
+                            arrival: process.arrival,
+                            burst: process.burst,
+                            priority: process.priority,
+                            deadline: process.deadline,
+                            io: 0,
+                            blocked: 0,
+                            response,
                             turnaround,
-                            waiting,
-                        });
-                    }
+                            waiting: turnaround - process.burst,
+                        }
+                    })
+                    .collect::<Vec<OutputProcessEntry>>();
+                let metrics = Metrics::new(
+                    multicore.name(),
+                    &output,
+                    multicore.cores as u32,
+                    0,
+                    0,
+                    self.throughput_window,
+                );
+                let deadlines = Feeder::parse_deadlines(&output);
+                let mut result = format!("{}\n", multicore.name());
+                result.push_str(&Feeder::parse_output(output, &metrics));
+                if let Some(deadlines) = deadlines {
+                    result.push_str(&deadlines);
                 }
-                outputs.push(Feeder::parse_output(output));
+                result.push_str(&run.report());
+                outputs.push(result);
+                self.summaries.push(metrics);
             }
             outputs
         }